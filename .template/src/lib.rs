@@ -1,10 +1,12 @@
 #![feature(test)]
 extern crate test;
 
-#[cfg(debug_assertions)]
+use common::explain::Explanation;
+
+#[cfg(any(debug_assertions, not(puzzle_input)))]
 const INPUT: &str = include_str!("../sample.TXT");
 
-#[cfg(not(debug_assertions))]
+#[cfg(all(not(debug_assertions), puzzle_input))]
 const INPUT: &str = include_str!("../input.TXT");
 
 type ParsedInput = usize;
@@ -22,7 +24,11 @@ fn parse_input(input: &str) -> ParsedInput {
 }
 
 pub fn solve() {
-    let input = parse_input(INPUT);
+    solve_input(INPUT);
+}
+
+pub fn solve_input(input: &str) {
+    let input = parse_input(input);
 
     let result = solve_part_one(&input);
     println!("Part #1: {}", result);
@@ -31,6 +37,29 @@ pub fn solve() {
     println!("Part #2: {}", result);
 }
 
+common::export_plugin!({{year}}, {{day}}, solve_input);
+
+pub fn explain() -> Explanation {
+    explain_input(INPUT)
+}
+
+/// The intermediate results behind both answers.
+pub fn explain_input(input: &str) -> Explanation {
+    let input = parse_input(input);
+
+    let mut part_one = Explanation::new();
+    part_one.field("answer", solve_part_one(&input));
+
+    let mut part_two = Explanation::new();
+    part_two.field("answer", solve_part_two(&input));
+
+    let mut explanation = Explanation::new();
+    explanation
+        .field("part_one", part_one)
+        .field("part_two", part_two);
+    explanation
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -39,10 +68,10 @@ mod tests {
     fn test_part_one() {
         let result = solve_part_one(&parse_input(INPUT));
 
-        #[cfg(debug_assertions)]
+        #[cfg(any(debug_assertions, not(puzzle_input)))]
         assert_eq!(result, 4512);
 
-        #[cfg(not(debug_assertions))]
+        #[cfg(all(not(debug_assertions), puzzle_input))]
         assert_eq!(result, 27027);
     }
 
@@ -56,10 +85,10 @@ mod tests {
     fn test_part_two() {
         let result = solve_part_two(&parse_input(INPUT));
 
-        #[cfg(debug_assertions)]
+        #[cfg(any(debug_assertions, not(puzzle_input)))]
         assert_eq!(result, 1924);
 
-        #[cfg(not(debug_assertions))]
+        #[cfg(all(not(debug_assertions), puzzle_input))]
         assert_eq!(result, 36975);
     }
 
@@ -1,5 +1,22 @@
-use solution::solve;
+use common::{cli::Args, explain::Format};
+use solution::{explain, explain_input, solve, solve_input};
 
 fn main() {
-    solve();
+    let args = Args::from_env();
+    let input = args.read_input();
+
+    if args.explain != Some(Format::Json) {
+        match &input {
+            Some(input) => solve_input(input),
+            None => solve(),
+        }
+    }
+
+    if let Some(format) = args.explain {
+        let explanation = match &input {
+            Some(input) => explain_input(input),
+            None => explain(),
+        };
+        print!("{}", format.render(&explanation));
+    }
 }
@@ -1,5 +0,0 @@
-use solution::solve;
-
-fn main() {
-    solve();
-}
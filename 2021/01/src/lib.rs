@@ -1,3 +1,5 @@
+use common::explain::Explanation;
+
 #[cfg(debug_assertions)]
 const INPUT: &str = include_str!("../sample.TXT");
 
@@ -5,7 +7,11 @@ const INPUT: &str = include_str!("../sample.TXT");
 const INPUT: &str = include_str!("../input.TXT");
 
 pub fn solve() {
-    let input = parse_input(INPUT);
+    solve_input(INPUT);
+}
+
+pub fn solve_input(input: &str) {
+    let input = parse_input(input);
 
     let result = solve_part_one(&input);
     println!("Part #1: {}", result);
@@ -14,6 +20,8 @@ pub fn solve() {
     println!("Part #2: {}", result);
 }
 
+common::export_plugin!(2021, 1, solve_input);
+
 fn solve_part_one(input: &[u32]) -> u32 {
     let mut sum = 0;
     for (idx, i) in input.iter().enumerate() {
@@ -27,7 +35,7 @@ fn solve_part_one(input: &[u32]) -> u32 {
     sum
 }
 
-fn solve_part_two(input: &[u32]) -> u32 {
+fn window_sums(input: &[u32]) -> Vec<u32> {
     let win_len = 3;
     let mut sums = Vec::new();
     let mut start = 0;
@@ -40,7 +48,11 @@ fn solve_part_two(input: &[u32]) -> u32 {
         sums.push(win.iter().sum::<u32>());
         start += 1;
     }
-    solve_part_one(&sums)
+    sums
+}
+
+fn solve_part_two(input: &[u32]) -> u32 {
+    solve_part_one(&window_sums(input))
 }
 
 fn parse_input(input: &str) -> Vec<u32> {
@@ -51,6 +63,31 @@ fn parse_input(input: &str) -> Vec<u32> {
     vec
 }
 
+pub fn explain() -> Explanation {
+    explain_input(INPUT)
+}
+
+/// How many depths each part compares, and how many of them increase.
+pub fn explain_input(input: &str) -> Explanation {
+    let input = parse_input(input);
+    let sums = window_sums(&input);
+    let mut explanation = Explanation::new();
+
+    let mut part_one = Explanation::new();
+    part_one
+        .field("measurements", input.len())
+        .field("increases", solve_part_one(&input));
+    explanation.field("part_one", part_one);
+
+    let mut part_two = Explanation::new();
+    part_two
+        .field("windows", sums.len())
+        .field("increases", solve_part_one(&sums));
+    explanation.field("part_two", part_two);
+
+    explanation
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -68,4 +105,15 @@ mod tests {
 
         assert_eq!(result, 5);
     }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain_input(include_str!("../sample.TXT"));
+
+        assert_eq!(
+            explanation.text(),
+            "part_one:\n  measurements: 10\n  increases: 7\n\
+             part_two:\n  windows: 8\n  increases: 5\n"
+        );
+    }
 }
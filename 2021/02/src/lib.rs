@@ -0,0 +1,197 @@
+use std::str::FromStr;
+
+use common::{
+    arith::{self, Acc},
+    explain::Explanation,
+};
+
+#[cfg(debug_assertions)]
+const INPUT: &str = include_str!("../sample.TXT");
+
+#[cfg(not(debug_assertions))]
+const INPUT: &str = include_str!("../input.TXT");
+
+/// Signed, since nothing stops the submarine from steering above the surface.
+type Position = Acc<i64>;
+
+pub enum Instruction {
+    Forward(u32),
+    Up(u32),
+    Down(u32),
+}
+
+impl FromStr for Instruction {
+    type Err = Box<dyn std::error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ix = s.split_whitespace().collect::<Vec<_>>();
+
+        if let [ix, dist] = ix[..] {
+            return match ix {
+                "forward" => Ok(Instruction::Forward(dist.parse::<u32>()?)),
+                "up" => Ok(Instruction::Up(dist.parse::<u32>()?)),
+                "down" => Ok(Instruction::Down(dist.parse::<u32>()?)),
+                _ => unreachable!(),
+            };
+        }
+
+        Err(From::from("Invalid instruction"))
+    }
+}
+
+pub fn solve() {
+    solve_input(INPUT);
+}
+
+pub fn solve_input(input: &str) {
+    let input = parse_input(input);
+
+    match solve_part_one(&input) {
+        Ok(result) => println!("Part #1: {}", result),
+        Err(e) => println!("Part #1: {}", e),
+    }
+
+    match solve_part_two(&input) {
+        Ok(result) => println!("Part #2: {}", result),
+        Err(e) => println!("Part #2: {}", e),
+    }
+}
+
+common::export_plugin!(2021, 2, solve_input);
+
+/// Horizontal position and depth, steering directly.
+fn course_one(input: &[Instruction]) -> arith::Result<(Position, Position)> {
+    let x_pos = input
+        .iter()
+        .try_fold(Position::default(), |acc, ix| match ix {
+            Instruction::Forward(dist) => arith::add(acc, arith::acc(i64::from(*dist))),
+            _ => Ok(acc),
+        })?;
+
+    let depth = input
+        .iter()
+        .try_fold(Position::default(), |acc, ix| match ix {
+            Instruction::Up(dist) => arith::sub(acc, arith::acc(i64::from(*dist))),
+            Instruction::Down(dist) => arith::add(acc, arith::acc(i64::from(*dist))),
+            _ => Ok(acc),
+        })?;
+
+    Ok((x_pos, depth))
+}
+
+fn solve_part_one(input: &[Instruction]) -> arith::Result<Position> {
+    let (x_pos, depth) = course_one(input)?;
+    arith::mul(depth, x_pos)
+}
+
+/// Horizontal position, depth and aim, steering through the aim.
+fn course_two(input: &[Instruction]) -> arith::Result<(Position, Position, Position)> {
+    let mut aim = Position::default();
+    let mut x_pos = Position::default();
+    let mut depth = Position::default();
+
+    for ix in input {
+        match ix {
+            Instruction::Forward(dist) => {
+                let dist = arith::acc(i64::from(*dist));
+                x_pos = arith::add(x_pos, dist)?;
+                depth = arith::add(depth, arith::mul(aim, dist)?)?;
+            }
+            Instruction::Up(dist) => {
+                aim = arith::sub(aim, arith::acc(i64::from(*dist)))?;
+            }
+            Instruction::Down(dist) => {
+                aim = arith::add(aim, arith::acc(i64::from(*dist)))?;
+            }
+        }
+    }
+
+    Ok((x_pos, depth, aim))
+}
+
+fn solve_part_two(input: &[Instruction]) -> arith::Result<Position> {
+    let (x_pos, depth, _) = course_two(input)?;
+    arith::mul(depth, x_pos)
+}
+
+fn parse_input(input: &str) -> Vec<Instruction> {
+    input
+        .lines()
+        .map(|line| line.parse::<Instruction>().unwrap())
+        .collect()
+}
+
+pub fn explain() -> Explanation {
+    explain_input(INPUT)
+}
+
+/// Where the submarine ends up in each part.
+pub fn explain_input(input: &str) -> Explanation {
+    let input = parse_input(input);
+    let mut explanation = Explanation::new();
+
+    let mut part_one = Explanation::new();
+    match course_one(&input) {
+        Ok((x_pos, depth)) => part_one.field("position", x_pos).field("depth", depth),
+        Err(e) => part_one.field("error", e.to_string()),
+    };
+    explanation.field("part_one", part_one);
+
+    let mut part_two = Explanation::new();
+    match course_two(&input) {
+        Ok((x_pos, depth, aim)) => part_two
+            .field("position", x_pos)
+            .field("depth", depth)
+            .field("aim", aim),
+        Err(e) => part_two.field("error", e.to_string()),
+    };
+    explanation.field("part_two", part_two);
+
+    explanation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = solve_part_one(&parse_input(INPUT));
+
+        assert_eq!(result, Ok(150));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = solve_part_two(&parse_input(INPUT));
+
+        assert_eq!(result, Ok(900));
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain_input(include_str!("../sample.TXT"));
+
+        assert_eq!(
+            explanation.text(),
+            "part_one:\n  position: 15\n  depth: 10\n\
+             part_two:\n  position: 15\n  depth: 60\n  aim: 10\n"
+        );
+    }
+
+    #[test]
+    fn test_rising_above_surface() {
+        let input = parse_input("down 1\nup 3\nforward 2");
+
+        assert_eq!(solve_part_one(&input), Ok(-4));
+        assert_eq!(solve_part_two(&input), Ok(-8));
+    }
+
+    #[cfg(feature = "wide")]
+    #[test]
+    fn test_wide_negative_depth() {
+        let input = parse_input("up 4000000000\nforward 4000000000");
+
+        assert_eq!(solve_part_two(&input), Ok(-(4_000_000_000i128.pow(3))));
+    }
+}
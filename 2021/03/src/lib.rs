@@ -0,0 +1,154 @@
+use std::cmp::Ordering;
+
+use common::explain::Explanation;
+
+#[cfg(debug_assertions)]
+const INPUT: &str = include_str!("../sample.TXT");
+
+#[cfg(not(debug_assertions))]
+const INPUT: &str = include_str!("../input.TXT");
+
+fn other(bit: u8) -> u8 {
+    match bit {
+        b'1' => b'0',
+        b'0' => b'1',
+        _ => unreachable!(),
+    }
+}
+
+fn get_most_common_bit(numbers: &[&[u8]], col: usize) -> Option<u8> {
+    let ones_count = numbers.iter().filter(|n| n[col] == b'1').count();
+    let zeros_count = numbers.len() - ones_count;
+
+    match ones_count.cmp(&zeros_count) {
+        Ordering::Equal => None,
+        Ordering::Less => Some(b'0'),
+        Ordering::Greater => Some(b'1'),
+    }
+}
+
+fn to_number(bits: &[u8]) -> u32 {
+    bits.iter()
+        .fold(0, |n, &bit| (n << 1) | u32::from(bit == b'1'))
+}
+
+fn gamma_epsilon(input: &[&[u8]]) -> (u32, u32) {
+    (0..input[0].len()).fold((0, 0), |(gamma_rate, epsilon_rate), col| {
+        let most_common = get_most_common_bit(input, col).unwrap();
+        (
+            (gamma_rate << 1) | u32::from(most_common == b'1'),
+            (epsilon_rate << 1) | u32::from(other(most_common) == b'1'),
+        )
+    })
+}
+
+fn get_rate(bytes: &[&[u8]], tiebraker: u8) -> u32 {
+    let mut rate = Vec::from(bytes);
+    let byte_length = bytes[0].len();
+    let use_least_common_bit = tiebraker == b'0';
+
+    for col in 0..byte_length {
+        if rate.len() == 1 {
+            break;
+        }
+
+        let most_common_bit = get_most_common_bit(&rate, col);
+
+        let most_common_bit = if use_least_common_bit {
+            most_common_bit.map(other)
+        } else {
+            most_common_bit
+        };
+
+        rate.retain(|s| s[col] == most_common_bit.unwrap_or(tiebraker));
+    }
+
+    to_number(rate[0])
+}
+
+fn solve_part_one(input: &[&[u8]]) -> u32 {
+    let (gamma_rate, epsilon_rate) = gamma_epsilon(input);
+
+    gamma_rate * epsilon_rate
+}
+
+fn solve_part_two(input: &[&[u8]]) -> u32 {
+    let oxygen_rate = get_rate(input, b'1');
+    let co2_rate = get_rate(input, b'0');
+    oxygen_rate * co2_rate
+}
+
+fn parse_input(input: &str) -> Vec<&[u8]> {
+    input.lines().map(str::as_bytes).collect()
+}
+
+pub fn solve() {
+    solve_input(INPUT);
+}
+
+pub fn solve_input(input: &str) {
+    let input = parse_input(input);
+
+    let result = solve_part_one(&input);
+    println!("Part #1: {}", result);
+
+    let result = solve_part_two(&input);
+    println!("Part #2: {}", result);
+}
+
+common::export_plugin!(2021, 3, solve_input);
+
+pub fn explain() -> Explanation {
+    explain_input(INPUT)
+}
+
+/// The rates multiplied in each part.
+pub fn explain_input(input: &str) -> Explanation {
+    let input = parse_input(input);
+    let (gamma_rate, epsilon_rate) = gamma_epsilon(&input);
+    let mut explanation = Explanation::new();
+
+    let mut part_one = Explanation::new();
+    part_one
+        .field("gamma_rate", gamma_rate)
+        .field("epsilon_rate", epsilon_rate);
+    explanation.field("part_one", part_one);
+
+    let mut part_two = Explanation::new();
+    part_two
+        .field("oxygen_rating", get_rate(&input, b'1'))
+        .field("co2_rating", get_rate(&input, b'0'));
+    explanation.field("part_two", part_two);
+
+    explanation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = solve_part_one(&parse_input(INPUT));
+
+        assert_eq!(result, 198);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = solve_part_two(&parse_input(INPUT));
+
+        assert_eq!(result, 230);
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain_input(include_str!("../sample.TXT"));
+
+        assert_eq!(
+            explanation.text(),
+            "part_one:\n  gamma_rate: 22\n  epsilon_rate: 9\n\
+             part_two:\n  oxygen_rating: 23\n  co2_rating: 10\n"
+        );
+    }
+}
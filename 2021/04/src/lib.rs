@@ -3,6 +3,7 @@ extern crate test;
 
 use std::{fmt::Display, sync::Arc};
 
+use common::explain::Explanation;
 use threadpool::ThreadPool;
 
 #[cfg(debug_assertions)]
@@ -66,6 +67,19 @@ impl Board {
     pub fn get_points(&self) -> u32 {
         self.values.iter().filter(|&v| !is_marked(v)).sum::<u32>()
     }
+
+    /// Rows with marked numbers followed by a `*`.
+    pub fn marked_rows(&self) -> Vec<String> {
+        self.rows()
+            .map(|row| {
+                let cells = row.iter().map(|v| match is_marked(v) {
+                    true => format!("{:>3}*", v & !MARK_MASK),
+                    false => format!("{:>3} ", v),
+                });
+                cells.collect::<String>().trim_end().to_owned()
+            })
+            .collect()
+    }
 }
 
 impl PartialEq for Board {
@@ -158,7 +172,11 @@ fn solve_part_two((nums, boards): &ParsedInput) -> u32 {
 }
 
 pub fn solve() {
-    let input = parse_input(INPUT);
+    solve_input(INPUT);
+}
+
+pub fn solve_input(input: &str) {
+    let input = parse_input(input);
 
     let result = solve_part_one(&input);
     println!("Part #1: {}", result);
@@ -167,6 +185,8 @@ pub fn solve() {
     println!("Part #2: {}", result);
 }
 
+common::export_plugin!(2021, 4, solve_input);
+
 fn parse_input(input: &str) -> ParsedInput {
     let mut lines = input.lines();
     let nums: Vec<u32> = lines
@@ -195,6 +215,40 @@ fn parse_input(input: &str) -> ParsedInput {
     (nums, boards)
 }
 
+pub fn explain() -> Explanation {
+    explain_input(INPUT)
+}
+
+/// The first and the last board to win, with the round they won in.
+pub fn explain_input(input: &str) -> Explanation {
+    let (nums, mut boards) = parse_input(input);
+    let mut winners = vec![];
+
+    while !boards.is_empty() {
+        let winner = get_winning_board(&nums, &boards);
+        boards.retain(|b| b != &winner.1);
+        winners.push(winner);
+    }
+
+    let describe = |(round, board): &(usize, Board)| {
+        let mut explanation = Explanation::new();
+        explanation
+            .field("round", round + 1)
+            .field("number", nums[*round])
+            .field("board", board.marked_rows())
+            .field("score", board.get_points() * nums[*round]);
+        explanation
+    };
+
+    let mut explanation = Explanation::new();
+    if let (Some(first), Some(last)) = (winners.first(), winners.last()) {
+        explanation
+            .field("first_winner", describe(first))
+            .field("last_winner", describe(last));
+    }
+    explanation
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -230,4 +284,25 @@ mod tests {
     fn bench_part_two(b: &mut test::Bencher) {
         b.iter(|| solve_part_two(&parse_input(INPUT)));
     }
+
+    const SAMPLE: &str = include_str!("../sample.TXT");
+
+    #[test]
+    fn snapshot_boards() {
+        let (nums, boards) = parse_input(SAMPLE);
+        let boards = boards.iter().map(Board::to_string).collect::<Vec<_>>();
+        insta::assert_snapshot!("boards", boards.join("\n"));
+
+        let (last_round, winner) = get_winning_board(&nums, &parse_input(SAMPLE).1);
+        insta::assert_snapshot!(
+            "winning_board",
+            format!("after {}:\n{}", nums[last_round], winner)
+        );
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain_input(SAMPLE);
+        insta::assert_snapshot!("explanation", explanation.text());
+    }
 }
@@ -0,0 +1,328 @@
+#![feature(test)]
+
+use std::{fmt::Display, ops::RangeInclusive};
+extern crate test;
+
+use common::{
+    explain::Explanation,
+    geometry::Vec2,
+    parse::{self, pair, tag, vec2, Parser},
+    svg::{self, Svg},
+};
+
+#[cfg(debug_assertions)]
+const INPUT: &str = include_str!("../sample.TXT");
+
+#[cfg(not(debug_assertions))]
+const INPUT: &str = include_str!("../input.TXT");
+
+// (All coordinates parsed, Maximum x and y points)
+type ParsedInput = (Vec<Line>, Coord);
+
+type Coord = Vec2<isize>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Line {
+    pub start: Coord,
+    pub end: Coord,
+}
+
+struct Diagram {
+    lines: Vec<Vec<u8>>,
+    // Number of points visited at least twice
+    visited_twice_cnt: usize,
+}
+
+impl Display for Line {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} -> {}", self.start, self.end)
+    }
+}
+
+impl Display for Diagram {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "  ")?;
+        for x in 0..self.lines[0].len() {
+            write!(f, "{:2} ", x)?;
+        }
+        writeln!(f)?;
+        for (idx, row) in self.lines.iter().enumerate() {
+            write!(f, "{} ", idx)?;
+            for &cell in row {
+                if cell == 0 {
+                    write!(f, " . ")?;
+                } else {
+                    write!(f, "{:>2} ", cell)?;
+                }
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Line {
+    pub fn is_straight(&self) -> bool {
+        self.start.x == self.end.x || self.start.y == self.end.y
+    }
+
+    // Creates an inclusive range from two positions
+    fn range_from(p1: isize, p2: isize) -> RangeInclusive<usize> {
+        let min = p1.min(p2);
+        let max = p1.max(p2);
+
+        min as usize..=max as usize
+    }
+
+    pub fn as_ranges(&self) -> (RangeInclusive<usize>, RangeInclusive<usize>) {
+        let x_range = Self::range_from(self.start.x, self.end.x);
+        let y_range = Self::range_from(self.start.y, self.end.y);
+
+        (x_range, y_range)
+    }
+
+    pub fn displacement(&self) -> Coord {
+        self.end - self.start
+    }
+}
+
+impl Diagram {
+    pub fn new(max_coord: &Coord) -> Self {
+        Diagram {
+            lines: vec![vec![0; max_coord.x as usize + 1]; max_coord.y as usize + 1],
+            visited_twice_cnt: 0,
+        }
+    }
+
+    pub fn visit(&mut self, line: &Line) {
+        let (x_range, y_range) = line.as_ranges();
+
+        if !line.is_straight() {
+            return self.visit_angled(line);
+        }
+
+        self.lines[y_range].iter_mut().for_each(|row| {
+            for x in row[x_range.clone()].iter_mut() {
+                *x += 1;
+
+                if *x == 2 {
+                    self.visited_twice_cnt += 1;
+                }
+            }
+        });
+    }
+
+    fn visit_point(&mut self, x: usize, y: usize) {
+        self.lines[y][x] += 1;
+
+        if self.lines[y][x] == 2 {
+            self.visited_twice_cnt += 1;
+        }
+    }
+
+    pub fn visit_angled(&mut self, line: &Line) {
+        let step = line.displacement().signum();
+        let mut point = line.start;
+
+        loop {
+            self.visit_point(point.x as usize, point.y as usize);
+
+            if point == line.end {
+                break;
+            }
+
+            point += step;
+        }
+    }
+
+    pub fn count_visited_twice(&self) -> usize {
+        self.visited_twice_cnt
+    }
+}
+
+fn draw<'a>(lines: impl IntoIterator<Item = &'a Line>, max_coord: &Coord) -> Diagram {
+    let mut diagram = Diagram::new(max_coord);
+
+    for line in lines {
+        diagram.visit(line);
+    }
+
+    diagram
+}
+
+fn solve_part_one((lines, max_coord): &ParsedInput) -> usize {
+    let lines = lines.iter().filter(|l| l.is_straight());
+
+    draw(lines, max_coord).count_visited_twice()
+}
+
+fn solve_part_two((lines, max_coord): &ParsedInput) -> usize {
+    draw(lines, max_coord).count_visited_twice()
+}
+
+fn parse_input(input: &str) -> ParsedInput {
+    let line = pair(vec2().skip(tag(" -> ")), vec2()).map(|(start, end)| Line { start, end });
+    let lines = parse::parse(parse::lines(line), input).unwrap_or_else(|e| panic!("{}", e));
+
+    let max_coord = lines.iter().fold(Coord::default(), |max, line| {
+        max.max(line.start).max(line.end)
+    });
+
+    (lines, max_coord)
+}
+
+pub fn solve() {
+    solve_input(INPUT);
+}
+
+pub fn solve_input(input: &str) {
+    let input = parse_input(input);
+
+    let result = solve_part_one(&input);
+    println!("Part #1: {}", result);
+
+    let result = solve_part_two(&input);
+    println!("Part #2: {}", result);
+}
+
+common::export_plugin!(2021, 5, solve_input);
+
+pub fn svg() -> String {
+    svg_input(INPUT)
+}
+
+/// Every vent line, with the points where at least two of them overlap in
+/// red.
+pub fn svg_input(input: &str) -> String {
+    let (lines, max_coord) = parse_input(input);
+    let diagram = draw(&lines, &max_coord);
+
+    let (width, height) = (max_coord.x as usize + 1, max_coord.y as usize + 1);
+    let cell = svg::cell_size(width, height);
+    let centre = |p: Coord| ((p.x as f64 + 0.5) * cell, (p.y as f64 + 0.5) * cell);
+
+    let mut svg = Svg::new(width as f64 * cell, height as f64 * cell);
+    svg.rect(0.0, 0.0, width as f64 * cell, height as f64 * cell, "#fff");
+    for line in &lines {
+        svg.line(centre(line.start), centre(line.end), "#8899bb", cell * 0.4);
+    }
+    for (y, row) in diagram.lines.iter().enumerate() {
+        for (x, &count) in row.iter().enumerate() {
+            if count >= 2 {
+                svg.rect(x as f64 * cell, y as f64 * cell, cell, cell, "#d22");
+            }
+        }
+    }
+
+    svg.to_string()
+}
+
+pub fn explain() -> Explanation {
+    explain_input(INPUT)
+}
+
+/// How many lines each part draws, how many points they overlap at and the
+/// most lines crossing a single point.
+pub fn explain_input(input: &str) -> Explanation {
+    let (lines, max_coord) = parse_input(input);
+    let straight = lines.iter().filter(|l| l.is_straight()).collect::<Vec<_>>();
+    let mut explanation = Explanation::new();
+
+    for (part, lines) in [("part_one", straight), ("part_two", lines.iter().collect())] {
+        let diagram = draw(lines.iter().copied(), &max_coord);
+        let most = diagram.lines.iter().flatten().max().copied().unwrap_or(0);
+
+        let mut details = Explanation::new();
+        details
+            .field("lines", lines.len())
+            .field("overlaps", diagram.count_visited_twice())
+            .field("most_lines_at_a_point", most);
+        explanation.field(part, details);
+    }
+
+    explanation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = solve_part_one(&parse_input(INPUT));
+
+        #[cfg(debug_assertions)]
+        assert_eq!(result, 5);
+
+        #[cfg(not(debug_assertions))]
+        assert_eq!(result, 7644);
+    }
+
+    #[bench]
+    fn bench_part_one(b: &mut test::Bencher) {
+        b.iter(|| solve_part_one(&parse_input(INPUT)));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = solve_part_two(&parse_input(INPUT));
+
+        #[cfg(debug_assertions)]
+        assert_eq!(result, 12);
+
+        #[cfg(not(debug_assertions))]
+        assert_eq!(result, 18627);
+    }
+
+    #[bench]
+    fn bench_part_two(b: &mut test::Bencher) {
+        b.iter(|| solve_part_two(&parse_input(INPUT)));
+    }
+
+    const SAMPLE: &str = include_str!("../sample.TXT");
+
+    #[test]
+    fn snapshot_lines() {
+        let (lines, max_coord) = parse_input(SAMPLE);
+        let lines = lines.iter().map(Line::to_string).collect::<Vec<_>>();
+
+        insta::assert_snapshot!("lines", format!("{}\nmax: {}", lines.join("\n"), max_coord));
+    }
+
+    #[test]
+    fn snapshot_diagrams() {
+        let (lines, max_coord) = parse_input(SAMPLE);
+        let mut diagram = Diagram::new(&max_coord);
+
+        for line in lines.iter().filter(|l| l.is_straight()) {
+            diagram.visit(line);
+        }
+        insta::assert_snapshot!("straight_diagram", diagram);
+
+        for line in lines.iter().filter(|l| !l.is_straight()) {
+            diagram.visit(line);
+        }
+        insta::assert_snapshot!("full_diagram", diagram);
+    }
+
+    #[test]
+    fn svg_overlaps() {
+        let svg = svg_input(SAMPLE);
+
+        assert_eq!(svg.matches("<line ").count(), 10);
+        // The 12 overlapping points and the background.
+        assert_eq!(svg.matches("<rect ").count(), 13);
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain_input(SAMPLE);
+
+        assert_eq!(
+            explanation.text(),
+            "part_one:\n  lines: 6\n  overlaps: 5\n  most_lines_at_a_point: 2\n\
+             part_two:\n  lines: 10\n  overlaps: 12\n  most_lines_at_a_point: 3\n"
+        );
+    }
+}
@@ -2,6 +2,8 @@
 
 extern crate test;
 
+use common::explain::Explanation;
+
 #[cfg(debug_assertions)]
 const INPUT: &str = include_str!("../sample.TXT");
 
@@ -31,22 +33,30 @@ fn update_generation(state: &[u64]) -> Vec<u64> {
     new_state
 }
 
-fn get_population_count(days: u64, initial_state: &[u64]) -> u64 {
+const DAYS_PART_ONE: u64 = 80;
+const DAYS_PART_TWO: u64 = 256;
+
+/// Number of lanternfish by internal timer after `days`.
+fn population_after(days: u64, initial_state: &[u64]) -> Vec<u64> {
     let mut initial_state = initial_state.to_vec();
 
     for _ in 0..days {
         initial_state = update_generation(&initial_state);
     }
 
-    initial_state.iter().sum()
+    initial_state
+}
+
+fn get_population_count(days: u64, initial_state: &[u64]) -> u64 {
+    population_after(days, initial_state).iter().sum()
 }
 
 fn solve_part_one(input: &ParsedInput) -> u64 {
-    get_population_count(80, input)
+    get_population_count(DAYS_PART_ONE, input)
 }
 
 fn solve_part_two(input: &ParsedInput) -> u64 {
-    get_population_count(256, input)
+    get_population_count(DAYS_PART_TWO, input)
 }
 
 fn parse_input(input: &str) -> [u64; 9] {
@@ -68,7 +78,11 @@ fn dump_population(state: &[u64]) {
 }
 
 pub fn solve() {
-    let input = parse_input(INPUT);
+    solve_input(INPUT);
+}
+
+pub fn solve_input(input: &str) {
+    let input = parse_input(input);
 
     let result = solve_part_one(&input);
     println!("Part #1: {}", result);
@@ -77,6 +91,33 @@ pub fn solve() {
     println!("Part #2: {}", result);
 }
 
+common::export_plugin!(2021, 6, solve_input);
+
+pub fn explain() -> Explanation {
+    explain_input(INPUT)
+}
+
+/// The school after the days of each part, as the number of fish with each
+/// timer value from 0 to 8.
+pub fn explain_input(input: &str) -> Explanation {
+    let input = parse_input(input);
+    let mut explanation = Explanation::new();
+    explanation.field("initial_fish", input.iter().sum::<u64>());
+
+    for (part, days) in [("part_one", DAYS_PART_ONE), ("part_two", DAYS_PART_TWO)] {
+        let timers = population_after(days, &input);
+
+        let mut details = Explanation::new();
+        details
+            .field("days", days)
+            .field("fish", timers.iter().sum::<u64>())
+            .field("by_timer", timers);
+        explanation.field(part, details);
+    }
+
+    explanation
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -112,4 +153,17 @@ mod tests {
     fn bench_part_two(b: &mut test::Bencher) {
         b.iter(|| solve_part_two(&parse_input(INPUT)));
     }
+
+    #[test]
+    fn test_explain() {
+        let text = explain_input(include_str!("../sample.TXT")).text();
+
+        assert!(text.starts_with("initial_fish: 5\npart_one:\n  days: 80\n  fish: 5934\n"));
+        assert!(text.contains("part_two:\n  days: 256\n  fish: 26984457539\n"));
+        // After a single day, the only fish at 1 has become the one at 0.
+        assert_eq!(
+            population_after(1, &parse_input("3,4,3,1,2")),
+            [1, 1, 2, 1, 0, 0, 0, 0, 0]
+        );
+    }
 }
@@ -0,0 +1,175 @@
+#![feature(test)]
+
+extern crate test;
+
+use common::{
+    arith::{self, Acc},
+    explain::Explanation,
+};
+
+#[cfg(debug_assertions)]
+const INPUT: &str = include_str!("../sample.TXT");
+
+#[cfg(not(debug_assertions))]
+pub(crate) const INPUT: &str = include_str!("../input.TXT");
+
+type ParsedInput = Vec<u32>;
+
+fn median(vec: &[u32]) -> u32 {
+    let mut vec = vec.to_vec();
+    vec.sort_unstable();
+
+    match vec.len() % 2 {
+        0 => (vec[vec.len() / 2 - 1] + vec[vec.len() / 2]) / 2,
+        _ => vec[vec.len() / 2],
+    }
+}
+
+fn mean(vec: &[u32]) -> f64 {
+    vec.iter().sum::<u32>() as f64 / vec.len() as f64
+}
+
+fn solve_part_one(input: &ParsedInput) -> u32 {
+    let pos = median(input);
+
+    input
+        .iter()
+        .fold(0, |fuel, &crab_pos| fuel + u32::abs_diff(crab_pos, pos))
+}
+
+fn triangular_fuel(input: &ParsedInput, pos: u32) -> arith::Result<Acc<u32>> {
+    input
+        .iter()
+        .try_fold(Acc::<u32>::default(), |fuel, &crab_pos| {
+            let diff = arith::acc(u32::abs_diff(crab_pos, pos));
+            let cost = arith::mul(diff, arith::add(diff, 1)?)? / 2;
+            arith::add(fuel, cost)
+        })
+}
+
+/// Cheapest position under the triangular fuel cost, with its cost.
+fn best_triangular(input: &ParsedInput) -> arith::Result<(u32, Acc<u32>)> {
+    let pos = mean(input);
+
+    // The best position is within half a step of the mean, on either side.
+    let floor = f64::floor(pos) as u32;
+    let ceil = f64::ceil(pos) as u32;
+    let floor = (floor, triangular_fuel(input, floor)?);
+    let ceil = (ceil, triangular_fuel(input, ceil)?);
+
+    Ok(if ceil.1 < floor.1 { ceil } else { floor })
+}
+
+fn solve_part_two(input: &ParsedInput) -> arith::Result<Acc<u32>> {
+    Ok(best_triangular(input)?.1)
+}
+
+fn parse_input(input: &str) -> ParsedInput {
+    input
+        .split(',')
+        .map(|s| s.trim().parse().unwrap())
+        .collect::<Vec<_>>()
+}
+
+pub fn solve() {
+    solve_input(INPUT);
+}
+
+pub fn solve_input(input: &str) {
+    let input = parse_input(input);
+
+    let result = solve_part_one(&input);
+    println!("Part #1: {}", result);
+
+    match solve_part_two(&input) {
+        Ok(result) => println!("Part #2: {}", result),
+        Err(e) => println!("Part #2: {}", e),
+    }
+}
+
+common::export_plugin!(2021, 7, solve_input);
+
+pub fn explain() -> Explanation {
+    explain_input(INPUT)
+}
+
+/// The alignment position chosen for each part, and the fuel it costs.
+pub fn explain_input(input: &str) -> Explanation {
+    let input = parse_input(input);
+    let mut explanation = Explanation::new();
+
+    let mut part_one = Explanation::new();
+    part_one
+        .field("position", median(&input))
+        .field("fuel", solve_part_one(&input));
+    explanation.field("part_one", part_one);
+
+    let mut part_two = Explanation::new();
+    match best_triangular(&input) {
+        Ok((position, fuel)) => part_two.field("position", position).field("fuel", fuel),
+        Err(e) => part_two.field("error", e.to_string()),
+    };
+    explanation.field("part_two", part_two);
+
+    explanation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = solve_part_one(&parse_input(INPUT));
+
+        #[cfg(debug_assertions)]
+        assert_eq!(result, 37);
+
+        #[cfg(not(debug_assertions))]
+        assert_eq!(result, 347011);
+    }
+
+    #[bench]
+    fn bench_part_one(b: &mut test::Bencher) {
+        b.iter(|| solve_part_one(&parse_input(INPUT)));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = solve_part_two(&parse_input(INPUT));
+
+        #[cfg(debug_assertions)]
+        assert_eq!(result, Ok(168));
+
+        #[cfg(not(debug_assertions))]
+        assert_eq!(result, Ok(98363777));
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain_input(include_str!("../sample.TXT"));
+
+        assert_eq!(
+            explanation.text(),
+            "part_one:\n  position: 2\n  fuel: 37\npart_two:\n  position: 5\n  fuel: 168\n"
+        );
+    }
+
+    #[bench]
+    fn bench_part_two(b: &mut test::Bencher) {
+        b.iter(|| solve_part_two(&parse_input(INPUT)));
+    }
+
+    // Two crabs 200000 apart both need 5000050000 fuel to meet halfway.
+    #[cfg(all(feature = "checked", not(feature = "wide")))]
+    #[test]
+    fn test_part_two_overflow() {
+        assert_eq!(solve_part_two(&vec![0, 200000]), Err(arith::Overflow));
+    }
+
+    #[cfg(feature = "wide")]
+    #[test]
+    fn test_part_two_wide() {
+        assert_eq!(solve_part_two(&vec![0, 200000]), Ok(2 * 5000050000));
+    }
+}
@@ -1,9 +1,10 @@
 #![feature(test)]
 extern crate test;
 
-use std::str::FromStr;
+use std::{fmt::Display, str::FromStr};
 
 use bitflags::bitflags;
+use common::explain::Explanation;
 
 #[cfg(debug_assertions)]
 const INPUT: &str = include_str!("../sample.TXT");
@@ -47,6 +48,25 @@ impl FromStr for Segments {
     }
 }
 
+impl Display for Segments {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for (segment, c) in [
+            (Segments::A, 'a'),
+            (Segments::B, 'b'),
+            (Segments::C, 'c'),
+            (Segments::D, 'd'),
+            (Segments::E, 'e'),
+            (Segments::F, 'f'),
+            (Segments::G, 'g'),
+        ] {
+            if self.contains(segment) {
+                write!(f, "{}", c)?;
+            }
+        }
+        Ok(())
+    }
+}
+
 #[inline]
 fn common_segments_count(a: Segments, b: Segments) -> u32 {
     a.intersection(b).bits().count_ones()
@@ -136,14 +156,13 @@ fn solve_part_two(input: &ParsedInput) -> usize {
     sum
 }
 
-fn parse_input(input: &str) -> ParsedInput {
+fn parse_input(input: &str) -> ParsedInput<'_> {
     input
         .lines()
         .map(|line| {
             line.split('|')
                 .map(|s| {
-                    s.trim()
-                        .split_whitespace()
+                    s.split_whitespace()
                         .map(|s| s.parse::<Segments>().unwrap())
                         .collect()
                 })
@@ -153,7 +172,11 @@ fn parse_input(input: &str) -> ParsedInput {
 }
 
 pub fn solve() {
-    let input = parse_input(INPUT);
+    solve_input(INPUT);
+}
+
+pub fn solve_input(input: &str) {
+    let input = parse_input(input);
 
     let result = solve_part_one(&input);
     println!("Part #1: {}", result);
@@ -162,6 +185,41 @@ pub fn solve() {
     println!("Part #2: {}", result);
 }
 
+common::export_plugin!(2021, 8, solve_input);
+
+pub fn explain() -> Explanation {
+    explain_input(INPUT)
+}
+
+/// The segments lighting up each digit in every entry, and the decoded
+/// output value.
+pub fn explain_input(input: &str) -> Explanation {
+    let entries = parse_input(input)
+        .iter()
+        .filter_map(|entry| {
+            let [signals, output] = &entry[..] else {
+                return None;
+            };
+            let decoded = decode_signals(signals);
+
+            let mut digits = Explanation::new();
+            for (digit, segments) in decoded.iter().enumerate() {
+                digits.field(&digit.to_string(), segments.to_string());
+            }
+
+            let mut explanation = Explanation::new();
+            explanation
+                .field("digits", digits)
+                .field("output", decode_output(&decoded, output));
+            Some(explanation)
+        })
+        .collect::<Vec<_>>();
+
+    let mut explanation = Explanation::new();
+    explanation.field("entries", entries);
+    explanation
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -199,4 +257,16 @@ mod tests {
         let input = parse_input(INPUT);
         b.iter(|| solve_part_two(&input));
     }
+
+    #[test]
+    fn test_explain() {
+        let input = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | \
+                     cdfeb fcadb cdfeb cdbaf";
+        let explanation = explain_input(input);
+
+        assert_eq!(
+            explanation.json(),
+            r#"{"entries":[{"digits":{"0":"abcdeg","1":"ab","2":"acdfg","3":"abcdf","4":"abef","5":"bcdef","6":"bcdefg","7":"abd","8":"abcdefg","9":"abcdef"},"output":5353}]}"#
+        );
+    }
 }
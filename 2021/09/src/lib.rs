@@ -0,0 +1,243 @@
+#![feature(test)]
+extern crate test;
+
+use common::{
+    explain::Explanation,
+    geometry::Vec2,
+    grid::Grid,
+    search,
+    svg::{self, Svg},
+};
+
+#[cfg(debug_assertions)]
+const INPUT: &str = include_str!("../sample.TXT");
+
+#[cfg(not(debug_assertions))]
+const INPUT: &str = include_str!("../input.TXT");
+
+type ParsedInput<'a> = Grid<'a>;
+
+type Point = Vec2<usize>;
+
+fn is_low_point(mat: &Grid, p: Point) -> bool {
+    p.neighbours(mat.bounds())
+        .all(|n| mat.digit(n) > mat.digit(p))
+}
+
+fn find_low_points(mat: &Grid) -> Vec<Point> {
+    let mut low_points = Vec::new();
+
+    for y in 0..mat.height() {
+        for x in 0..mat.width() {
+            if is_low_point(mat, Point::new(x, y)) {
+                low_points.push(Point::new(x, y));
+            }
+        }
+    }
+
+    low_points
+}
+
+// Flood fill from the low point, flowing uphill until reaching a 9.
+fn find_basin(mat: &Grid, low_point: Point) -> Vec<Point> {
+    let uphill = |&p: &Point| {
+        p.neighbours(mat.bounds())
+            .filter(move |&n| mat.digit(n) != 9 && mat.digit(n) >= mat.digit(p))
+    };
+
+    search::bfs(low_point, uphill, |_| false)
+        .costs
+        .into_keys()
+        .collect()
+}
+
+fn solve_part_one(input: &ParsedInput) -> u32 {
+    let mut risk_level = 0u32;
+
+    for p in find_low_points(input) {
+        risk_level += (input.digit(p) + 1) as u32;
+    }
+
+    risk_level
+}
+
+fn solve_part_two(input: &ParsedInput) -> usize {
+    let mut counts = vec![];
+
+    for low_point in find_low_points(input) {
+        counts.push(find_basin(input, low_point).len());
+    }
+
+    counts.sort_unstable();
+
+    counts.iter().rev().take(3).product()
+}
+
+fn parse_input(input: &str) -> ParsedInput<'_> {
+    Grid::digits(input).unwrap_or_else(|e| panic!("{}", e))
+}
+
+pub fn solve() {
+    solve_input(INPUT);
+}
+
+pub fn solve_input(input: &str) {
+    let input = parse_input(input);
+
+    let result = solve_part_one(&input);
+    println!("Part #1: {}", result);
+
+    let result = solve_part_two(&input);
+    println!("Part #2: {}", result);
+}
+
+common::export_plugin!(2021, 9, solve_input);
+
+pub fn svg() -> String {
+    svg_input(INPUT)
+}
+
+/// The heightmap in shades of grey, with the three largest basins in
+/// colour and the low points as red dots.
+pub fn svg_input(input: &str) -> String {
+    let mat = parse_input(input);
+    let low_points = find_low_points(&mat);
+
+    let mut basins = low_points
+        .iter()
+        .map(|&p| find_basin(&mat, p))
+        .collect::<Vec<_>>();
+    basins.sort_unstable_by_key(|basin| std::cmp::Reverse(basin.len()));
+    let largest = basins
+        .iter()
+        .take(3)
+        .flatten()
+        .collect::<std::collections::HashSet<_>>();
+
+    let bounds = mat.bounds();
+    let cell = svg::cell_size(bounds.x, bounds.y);
+    let mut svg = Svg::new(bounds.x as f64 * cell, bounds.y as f64 * cell);
+
+    for (y, row) in mat.rows().enumerate() {
+        for (x, &h) in row.iter().enumerate() {
+            let h = h - b'0';
+            let fill = if largest.contains(&Point::new(x, y)) {
+                format!("hsl(30, 90%, {}%)", 75 - h as u32 * 5)
+            } else {
+                svg::grey(h as u32, 9)
+            };
+            svg.rect(x as f64 * cell, y as f64 * cell, cell, cell, &fill);
+        }
+    }
+    for p in low_points {
+        let (x, y) = ((p.x as f64 + 0.5) * cell, (p.y as f64 + 0.5) * cell);
+        svg.circle(x, y, cell / 3.0, "#d22");
+    }
+
+    svg.to_string()
+}
+
+pub fn explain() -> Explanation {
+    explain_input(INPUT)
+}
+
+/// The low points with their heights, and the sizes of the basins around
+/// them from largest to smallest.
+pub fn explain_input(input: &str) -> Explanation {
+    let mat = parse_input(input);
+    let low_points = find_low_points(&mat);
+    let mut explanation = Explanation::new();
+
+    let heights = low_points
+        .iter()
+        .map(|&p| format!("{},{}: {}", p.x, p.y, mat.digit(p)))
+        .collect::<Vec<_>>();
+    let mut part_one = Explanation::new();
+    part_one.field("low_points", heights);
+    explanation.field("part_one", part_one);
+
+    let mut sizes = low_points
+        .iter()
+        .map(|&p| find_basin(&mat, p).len())
+        .collect::<Vec<_>>();
+    sizes.sort_unstable_by(|a, b| b.cmp(a));
+    let mut part_two = Explanation::new();
+    part_two.field("basin_sizes", sizes);
+    explanation.field("part_two", part_two);
+
+    explanation
+}
+
+#[allow(unused)]
+fn dump_basin(mat: &Grid, basin: &[Point]) {
+    for (y, row) in mat.rows().enumerate() {
+        for (x, &num) in row.iter().enumerate() {
+            if basin.contains(&Point::new(x, y)) {
+                print!("#");
+            } else {
+                print!("{}", num as char);
+            }
+        }
+        println!();
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = solve_part_one(&parse_input(INPUT));
+
+        #[cfg(debug_assertions)]
+        assert_eq!(result, 15);
+
+        #[cfg(not(debug_assertions))]
+        assert_eq!(result, 550);
+    }
+
+    #[bench]
+    fn bench_part_one(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_one(&input));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = solve_part_two(&parse_input(INPUT));
+
+        #[cfg(debug_assertions)]
+        assert_eq!(result, 1134);
+
+        #[cfg(not(debug_assertions))]
+        assert_eq!(result, 1100682);
+    }
+
+    #[bench]
+    fn bench_part_two(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_two(&input));
+    }
+
+    #[test]
+    fn svg_basins() {
+        let svg = svg_input(include_str!("../sample.TXT"));
+
+        assert_eq!(svg.matches("<rect ").count(), 50);
+        assert_eq!(svg.matches("<circle ").count(), 4);
+        // The basins of sizes 14, 9 and 9.
+        assert_eq!(svg.matches("hsl(").count(), 32);
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain_input(include_str!("../sample.TXT"));
+
+        assert_eq!(
+            explanation.json(),
+            r#"{"part_one":{"low_points":["1,0: 1","9,0: 0","2,2: 5","6,4: 5"]},"part_two":{"basin_sizes":[14,9,9,3]}}"#
+        );
+    }
+}
@@ -0,0 +1,30 @@
+use common::{cli::Args, explain::Format};
+use solution::{explain, explain_input, solve, solve_input, svg, svg_input};
+
+fn main() {
+    let args = Args::from_env();
+    let input = args.read_input();
+
+    if args.svg {
+        match &input {
+            Some(input) => print!("{}", svg_input(input)),
+            None => print!("{}", svg()),
+        }
+        return;
+    }
+
+    if args.explain != Some(Format::Json) {
+        match &input {
+            Some(input) => solve_input(input),
+            None => solve(),
+        }
+    }
+
+    if let Some(format) = args.explain {
+        let explanation = match &input {
+            Some(input) => explain_input(input),
+            None => explain(),
+        };
+        print!("{}", format.render(&explanation));
+    }
+}
@@ -0,0 +1,209 @@
+#![feature(test)]
+extern crate test;
+
+use common::explain::Explanation;
+
+#[cfg(debug_assertions)]
+const INPUT: &str = include_str!("../sample.TXT");
+
+#[cfg(not(debug_assertions))]
+const INPUT: &str = include_str!("../input.TXT");
+
+type ParsedInput<'a> = Vec<&'a [u8]>;
+
+type ParseResult<T> = Result<T, Error>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    CorruptedChunk {
+        at: u8,
+    },
+    /// The closing delimiters the line is missing, innermost first.
+    UnterminatedChunk {
+        expected: Vec<u8>,
+    },
+}
+
+fn closed(tk: u8) -> Option<u8> {
+    match tk {
+        b'(' => Some(b')'),
+        b'[' => Some(b']'),
+        b'{' => Some(b'}'),
+        b'<' => Some(b'>'),
+        _ => None,
+    }
+}
+
+// Keeps the closing delimiters of the open chunks on a stack.
+fn parse_chunks(line: &[u8]) -> ParseResult<()> {
+    let mut expected = Vec::new();
+
+    for &tk in line {
+        match closed(tk) {
+            Some(delim) => expected.push(delim),
+            None if expected.last() == Some(&tk) => {
+                expected.pop();
+            }
+            None => return Err(Error::CorruptedChunk { at: tk }),
+        }
+    }
+
+    if expected.is_empty() {
+        Ok(())
+    } else {
+        expected.reverse();
+        Err(Error::UnterminatedChunk { expected })
+    }
+}
+
+fn corruption_score(found: u8) -> usize {
+    match found {
+        b')' => 3,
+        b']' => 57,
+        b'}' => 1197,
+        b'>' => 25137,
+        _ => panic!("Invalid delimiter {}", found as char),
+    }
+}
+
+fn completion_score(expected: &[u8]) -> usize {
+    expected.iter().fold(0, |score, delim| {
+        score * 5
+            + match delim {
+                b')' => 1,
+                b']' => 2,
+                b'}' => 3,
+                b'>' => 4,
+                _ => unreachable!(),
+            }
+    })
+}
+
+fn solve_part_one(input: &ParsedInput) -> usize {
+    let mut score = 0;
+    for line in input {
+        if let Err(Error::CorruptedChunk { at: found }) = parse_chunks(line) {
+            score += corruption_score(found);
+        }
+    }
+
+    score
+}
+
+fn solve_part_two(input: &ParsedInput) -> usize {
+    let mut scores = Vec::new();
+
+    for line in input {
+        if let Err(Error::UnterminatedChunk { expected }) = parse_chunks(line) {
+            scores.push(completion_score(&expected));
+        }
+    }
+
+    scores.sort_unstable();
+    scores[scores.len() / 2]
+}
+
+fn parse_input(input: &str) -> ParsedInput<'_> {
+    input.lines().map(str::as_bytes).collect()
+}
+
+pub fn solve() {
+    solve_input(INPUT);
+}
+
+pub fn solve_input(input: &str) {
+    let input = parse_input(input);
+
+    let result = solve_part_one(&input);
+    println!("Part #1: {}", result);
+
+    let result = solve_part_two(&input);
+    println!("Part #2: {}", result);
+}
+
+common::export_plugin!(2021, 10, solve_input);
+
+pub fn explain() -> Explanation {
+    explain_input(INPUT)
+}
+
+/// The illegal character of each corrupted line and the completion of each
+/// incomplete one, by line number, with their scores.
+pub fn explain_input(input: &str) -> Explanation {
+    let input = parse_input(input);
+    let (mut corrupted, mut incomplete) = (vec![], vec![]);
+
+    for (n, line) in input.iter().enumerate() {
+        match parse_chunks(line) {
+            Ok(()) => {}
+            Err(Error::CorruptedChunk { at }) => corrupted.push(format!(
+                "line {}: {} ({})",
+                n + 1,
+                at as char,
+                corruption_score(at)
+            )),
+            Err(Error::UnterminatedChunk { expected }) => incomplete.push(format!(
+                "line {}: {} ({})",
+                n + 1,
+                String::from_utf8_lossy(&expected),
+                completion_score(&expected)
+            )),
+        }
+    }
+
+    let mut explanation = Explanation::new();
+    explanation
+        .field("corrupted", corrupted)
+        .field("incomplete", incomplete);
+    explanation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = solve_part_one(&parse_input(INPUT));
+
+        #[cfg(debug_assertions)]
+        assert_eq!(result, 26397);
+
+        #[cfg(not(debug_assertions))]
+        assert_eq!(result, 374061);
+    }
+
+    #[bench]
+    fn bench_part_one(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_one(&input));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = solve_part_two(&parse_input(INPUT));
+
+        #[cfg(debug_assertions)]
+        assert_eq!(result, 288957);
+
+        #[cfg(not(debug_assertions))]
+        assert_eq!(result, 2116639949);
+    }
+
+    #[bench]
+    fn bench_part_two(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_two(&input));
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain_input(include_str!("../sample.TXT"));
+
+        assert_eq!(
+            explanation.json(),
+            r#"{"corrupted":["line 3: } (1197)","line 5: ) (3)","line 6: ] (57)","line 8: ) (3)","line 9: > (25137)"],"#.to_owned()
+                + r#""incomplete":["line 1: }}]])})] (288957)","line 2: )}>]}) (5566)","line 4: }}>}>)))) (1480781)","line 7: ]]}}]}]}> (995444)","line 10: ])}> (294)"]}"#
+        );
+    }
+}
@@ -1,6 +1,8 @@
 #![feature(test)]
 extern crate test;
 
+use common::explain::Explanation;
+
 #[cfg(debug_assertions)]
 const INPUT: &str = include_str!("../sample.TXT");
 
@@ -66,10 +68,12 @@ fn step(mat: &mut ParsedInput) -> usize {
     flashes
 }
 
+const STEPS_PART_ONE: usize = 100;
+
 fn solve_part_one(input: &ParsedInput) -> usize {
     let mut initial_state = *input;
 
-    (0..100).fold(0, |acc, _| acc + step(&mut initial_state))
+    (0..STEPS_PART_ONE).fold(0, |acc, _| acc + step(&mut initial_state))
 }
 
 fn solve_part_two(input: &ParsedInput) -> usize {
@@ -96,7 +100,11 @@ fn parse_input(input: &str) -> ParsedInput {
 }
 
 pub fn solve() {
-    let input = parse_input(INPUT);
+    solve_input(INPUT);
+}
+
+pub fn solve_input(input: &str) {
+    let input = parse_input(input);
 
     let result = solve_part_one(&input);
     println!("Part #1: {}", result);
@@ -105,6 +113,8 @@ pub fn solve() {
     println!("Part #2: {}", result);
 }
 
+common::export_plugin!(2021, 11, solve_input);
+
 pub fn dump(mat: [[u8; 10]; 10]) {
     for y in &mat {
         for x in y {
@@ -118,6 +128,41 @@ pub fn dump(mat: [[u8; 10]; 10]) {
     }
 }
 
+pub fn explain() -> Explanation {
+    explain_input(INPUT)
+}
+
+/// The energy levels after the first part's steps, and the first step on
+/// which every octopus flashes.
+pub fn explain_input(input: &str) -> Explanation {
+    let input = parse_input(input);
+    let mut state = input;
+    let flashes = (0..STEPS_PART_ONE).map(|_| step(&mut state)).sum::<usize>();
+    let rows = state
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|level| level.to_string())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut explanation = Explanation::new();
+
+    let mut part_one = Explanation::new();
+    part_one
+        .field("steps", STEPS_PART_ONE)
+        .field("flashes", flashes)
+        .field("energy_levels", rows);
+    explanation.field("part_one", part_one);
+
+    let mut part_two = Explanation::new();
+    part_two.field("synchronised_at_step", solve_part_two(&input));
+    explanation.field("part_two", part_two);
+
+    explanation
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -144,7 +189,7 @@ mod tests {
         let result = solve_part_two(&parse_input(INPUT));
 
         #[cfg(debug_assertions)]
-        assert_eq!(result, 1924);
+        assert_eq!(result, 195);
 
         #[cfg(not(debug_assertions))]
         assert_eq!(result, 249);
@@ -155,4 +200,18 @@ mod tests {
         let input = parse_input(INPUT);
         b.iter(|| solve_part_two(&input));
     }
+
+    #[test]
+    fn test_explain() {
+        let text = explain_input(include_str!("../sample.TXT")).text();
+
+        assert_eq!(
+            text,
+            "part_one:\n  steps: 100\n  flashes: 1656\n  energy_levels:\n    \
+             - 0397666866\n    - 0749766918\n    - 0053976933\n    - 0004297822\n    \
+             - 0004229892\n    - 0053222877\n    - 0532222966\n    - 9322228966\n    \
+             - 7922286866\n    - 6789998766\n\
+             part_two:\n  synchronised_at_step: 195\n"
+        );
+    }
 }
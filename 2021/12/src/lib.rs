@@ -0,0 +1,290 @@
+#![feature(test)]
+extern crate test;
+
+use std::{collections::HashMap, fs::OpenOptions, io::Write};
+
+use common::{
+    explain::Explanation,
+    parse::{self, pair, tag, word, Parser},
+    svg::Svg,
+};
+use petgraph::{
+    dot::{Config, Dot},
+    prelude::*,
+};
+
+#[cfg(debug_assertions)]
+const INPUT: &str = include_str!("../sample.TXT");
+
+#[cfg(not(debug_assertions))]
+const INPUT: &str = include_str!("../input.TXT");
+
+type ParsedInput<'i> = Graph<&'i str, usize, Undirected>;
+
+fn is_big_cave(cave: &str) -> bool {
+    cave.to_ascii_uppercase() == cave
+}
+
+fn count_all_paths_rec(
+    graph: &ParsedInput,
+    start: NodeIndex,
+    end: NodeIndex,
+    limit: u8,
+    buf: &mut Vec<NodeIndex>,
+    visited: &mut [u8],
+    count: &mut usize,
+) {
+    if !is_big_cave(graph[start]) {
+        if graph[start] == "start" {
+            visited[start.index()] = limit;
+        } else {
+            visited[start.index()] += 1;
+        }
+    }
+
+    buf.push(start);
+
+    if start == end {
+        // New path completed.
+        *count += 1;
+    } else {
+        for neighbor in graph.neighbors(start) {
+            if visited[neighbor.index()] < limit {
+                if visited[neighbor.index()] > 0 && visited[1..].contains(&limit) {
+                    continue;
+                }
+                count_all_paths_rec(graph, neighbor, end, limit, buf, visited, count);
+            }
+        }
+    }
+
+    if !is_big_cave(graph[start]) {
+        visited[start.index()] -= 1;
+    }
+
+    buf.pop();
+}
+
+fn count_all_paths(graph: &ParsedInput, limit: u8) -> usize {
+    let mut buf = Vec::new();
+    let mut visited = vec![0; graph.node_count()];
+    let mut count = 0;
+
+    count_all_paths_rec(
+        graph,
+        0.into(),
+        1.into(),
+        limit,
+        &mut buf,
+        &mut visited,
+        &mut count,
+    );
+
+    count
+}
+
+fn solve_part_one(input: &ParsedInput) -> usize {
+    count_all_paths(input, 1)
+}
+
+fn solve_part_two(input: &ParsedInput) -> usize {
+    count_all_paths(input, 2)
+}
+
+fn parse_input(input: &str) -> ParsedInput<'_> {
+    let mut graph = Graph::new_undirected();
+    let mut nodes = HashMap::new();
+
+    // Determine the start and end nodes
+    nodes.insert("start", graph.add_node("start"));
+    nodes.insert("end", graph.add_node("end"));
+
+    let edges = parse::lines(pair(word().skip(tag("-")), word()));
+
+    for (p1, p2) in parse::parse(edges, input).unwrap_or_else(|e| panic!("{}", e)) {
+        let p1 = *nodes.entry(p1).or_insert_with(|| graph.add_node(p1));
+        let p2 = *nodes.entry(p2).or_insert_with(|| graph.add_node(p2));
+        graph.add_edge(p1, p2, 1);
+    }
+
+    // dump_dotfile(&graph, "target/graph.dot");
+
+    graph
+}
+
+pub fn solve() {
+    solve_input(INPUT);
+}
+
+pub fn solve_input(input: &str) {
+    let input = parse_input(input);
+
+    let result = solve_part_one(&input);
+    println!("Part #1: {}", result);
+
+    let result = solve_part_two(&input);
+    println!("Part #2: {}", result);
+}
+
+common::export_plugin!(2021, 12, solve_input);
+
+pub fn svg() -> String {
+    svg_input(INPUT)
+}
+
+/// The caves on a circle, `start` on the left and `end` on the right, with
+/// big caves drawn larger.
+pub fn svg_input(input: &str) -> String {
+    const SIZE: f64 = 600.0;
+    const RADIUS: f64 = 240.0;
+
+    let graph = parse_input(input);
+    let (start, end) = (NodeIndex::new(0), NodeIndex::new(1));
+    let others = graph.node_indices().skip(2).collect::<Vec<_>>();
+    let (top, bottom) = others.split_at(others.len() / 2);
+
+    let order = [&[start], top, &[end], bottom].concat();
+    let mut positions = vec![(0.0, 0.0); graph.node_count()];
+    for (i, node) in order.iter().enumerate() {
+        let angle = std::f64::consts::PI * (1.0 + 2.0 * i as f64 / order.len() as f64);
+        positions[node.index()] = (
+            SIZE / 2.0 + RADIUS * angle.cos(),
+            SIZE / 2.0 + RADIUS * angle.sin(),
+        );
+    }
+
+    let mut svg = Svg::new(SIZE, SIZE);
+    for edge in graph.edge_references() {
+        svg.line(
+            positions[edge.source().index()],
+            positions[edge.target().index()],
+            "#999",
+            2.0,
+        );
+    }
+    for node in graph.node_indices() {
+        let (x, y) = positions[node.index()];
+        let (r, fill) = match graph[node] {
+            "start" | "end" => (24.0, "#8c8"),
+            cave if is_big_cave(cave) => (28.0, "#fb6"),
+            _ => (20.0, "#9bd"),
+        };
+        svg.circle(x, y, r, fill).text(x, y, 12.0, graph[node]);
+    }
+
+    svg.to_string()
+}
+
+pub fn explain() -> Explanation {
+    explain_input(INPUT)
+}
+
+/// The big and small caves, and how many paths each part finds through
+/// them.
+pub fn explain_input(input: &str) -> Explanation {
+    let graph = parse_input(input);
+    let mut caves = graph
+        .node_weights()
+        .copied()
+        .filter(|&cave| cave != "start" && cave != "end")
+        .collect::<Vec<_>>();
+    caves.sort_unstable();
+    let (big, small): (Vec<_>, Vec<_>) = caves.into_iter().partition(|cave| is_big_cave(cave));
+
+    let mut explanation = Explanation::new();
+    explanation
+        .field("big_caves", big)
+        .field("small_caves", small)
+        .field("connections", graph.edge_count());
+
+    for (part, limit) in [("part_one", 1), ("part_two", 2)] {
+        let mut details = Explanation::new();
+        details
+            .field("small_cave_visits", limit)
+            .field("paths", count_all_paths(&graph, limit));
+        explanation.field(part, details);
+    }
+
+    explanation
+}
+
+#[allow(unused)]
+fn dump_dotfile(graph: &ParsedInput, filename: &str) {
+    let mut fd = OpenOptions::open(
+        OpenOptions::new().create(true).write(true).append(false),
+        filename,
+    )
+    .expect("Failed to open file.");
+
+    fd.write_all(
+        Dot::with_config(&graph, &[Config::EdgeNoLabel])
+            .to_string()
+            .as_bytes(),
+    )
+    .expect("Failed to write graph to file.");
+
+    println!("Graph dotfile dumped to '{}'.", filename);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = solve_part_one(&parse_input(INPUT));
+
+        #[cfg(debug_assertions)]
+        assert_eq!(result, 226);
+
+        #[cfg(not(debug_assertions))]
+        assert_eq!(result, 5157);
+    }
+
+    #[bench]
+    fn bench_part_one(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_one(&input));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = solve_part_two(&parse_input(INPUT));
+
+        #[cfg(debug_assertions)]
+        assert_eq!(result, 3509);
+
+        #[cfg(not(debug_assertions))]
+        assert_eq!(result, 144309);
+    }
+
+    #[bench]
+    fn bench_part_two(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_two(&input));
+    }
+
+    #[test]
+    fn svg_graph() {
+        let svg = svg_input("start-A\nstart-b\nA-c\nA-b\nb-d\nA-end\nb-end");
+
+        assert_eq!(svg.matches("<line ").count(), 7);
+        assert_eq!(svg.matches("<circle ").count(), 6);
+        // `start` on the left and `end` on the right.
+        assert!(svg.contains(r#"<text x="60" y="300" "#));
+        assert!(svg.contains(r#"<text x="540" y="300" "#));
+        assert!(svg.contains(">end</text>"));
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain_input("start-A\nstart-b\nA-c\nA-b\nb-d\nA-end\nb-end");
+
+        assert_eq!(
+            explanation.text(),
+            "big_caves:\n  - A\nsmall_caves:\n  - b\n  - c\n  - d\nconnections: 7\n\
+             part_one:\n  small_cave_visits: 1\n  paths: 10\n\
+             part_two:\n  small_cave_visits: 2\n  paths: 36\n"
+        );
+    }
+}
@@ -0,0 +1,240 @@
+#![feature(test)]
+extern crate test;
+use std::collections::HashSet;
+
+use common::{
+    explain::Explanation,
+    geometry::Vec2,
+    parse::{self, integer, pair, preceded, satisfy, section_break, tag, vec2, Parser},
+    svg::{self, Svg},
+};
+
+#[cfg(debug_assertions)]
+const INPUT: &str = include_str!("../sample.TXT");
+
+#[cfg(not(debug_assertions))]
+const INPUT: &str = include_str!("../input.TXT");
+
+#[derive(Clone, Copy)]
+pub enum Fold {
+    X(usize),
+    Y(usize),
+}
+
+impl std::fmt::Display for Fold {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Fold::X(at) => write!(f, "x={}", at),
+            Fold::Y(at) => write!(f, "y={}", at),
+        }
+    }
+}
+
+pub type Point = Vec2<usize>;
+
+type ParsedInput = (HashSet<Point>, Vec<Fold>);
+
+fn fold_at(points: &HashSet<Point>, fold: Fold) -> HashSet<Point> {
+    points
+        .iter()
+        .map(|&Point { x, y }| match fold {
+            Fold::X(f) => Point::new(f.abs_diff(f.abs_diff(x)), y),
+            Fold::Y(f) => Point::new(x, f.abs_diff(f.abs_diff(y))),
+        })
+        .collect()
+}
+
+fn solve_part_one((points, folds): &ParsedInput) -> usize {
+    fold_at(points, *folds.first().unwrap()).len()
+}
+
+fn solve_part_two((points, folds): &ParsedInput) -> usize {
+    let points = folds
+        .iter()
+        .fold(points.clone(), |acc, fold| fold_at(&acc, *fold));
+
+    print!("{}", dump_sheet(&points));
+
+    0
+}
+
+fn parse_input(input: &str) -> ParsedInput {
+    let axis = satisfy(|c| c == 'x' || c == 'y', "axis");
+    let fold =
+        preceded(tag("fold along "), pair(axis.skip(tag("=")), integer())).map(|(axis, num)| {
+            match axis {
+                'x' => Fold::X(num),
+                _ => Fold::Y(num),
+            }
+        });
+    let sheet = pair(
+        parse::lines(vec2()).skip(section_break()),
+        parse::lines(fold),
+    );
+
+    let (points, folds) = parse::parse(sheet, input).unwrap_or_else(|e| panic!("{}", e));
+
+    (points.into_iter().collect(), folds)
+}
+
+pub fn solve() {
+    solve_input(INPUT);
+}
+
+pub fn solve_input(input: &str) {
+    let input = parse_input(input);
+
+    let result = solve_part_one(&input);
+    println!("Part #1: {}", result);
+
+    println!("Part #2:");
+    solve_part_two(&input);
+}
+
+common::export_plugin!(2021, 13, solve_input);
+
+fn dump_sheet(points: &HashSet<Point>) -> String {
+    let size = points
+        .iter()
+        .fold(Point::default(), |size, &point| size.max(point));
+
+    let mut grid = vec![vec![false; size.x + 1]; size.y + 1];
+
+    for Point { x, y } in points {
+        grid[*y][*x] = true;
+    }
+
+    let mut sheet = String::new();
+    for row in grid {
+        for cell in row {
+            sheet.push(if cell { '▓' } else { '░' });
+        }
+        sheet.push('\n');
+    }
+    sheet
+}
+
+pub fn explain() -> Explanation {
+    explain_input(INPUT)
+}
+
+/// The dots left after the first fold, and the sheet once every fold is
+/// done.
+pub fn explain_input(input: &str) -> Explanation {
+    let (points, folds) = parse_input(input);
+    let first = fold_at(&points, folds[0]);
+    let last = folds[1..]
+        .iter()
+        .fold(first.clone(), |acc, fold| fold_at(&acc, *fold));
+
+    let mut part_one = Explanation::new();
+    part_one
+        .field("fold", folds[0].to_string())
+        .field("dots", first.len());
+
+    let mut part_two = Explanation::new();
+    part_two.field("dots", last.len()).field(
+        "sheet",
+        dump_sheet(&last)
+            .lines()
+            .map(String::from)
+            .collect::<Vec<_>>(),
+    );
+
+    let mut explanation = Explanation::new();
+    explanation
+        .field("dots", points.len())
+        .field(
+            "folds",
+            folds.iter().map(Fold::to_string).collect::<Vec<_>>(),
+        )
+        .field("part_one", part_one)
+        .field("part_two", part_two);
+    explanation
+}
+
+pub fn svg() -> String {
+    svg_input(INPUT)
+}
+
+/// The sheet after every fold.
+pub fn svg_input(input: &str) -> String {
+    let (points, folds) = parse_input(input);
+    let points = folds.iter().fold(points, |acc, fold| fold_at(&acc, *fold));
+
+    let size = points
+        .iter()
+        .fold(Point::default(), |size, &point| size.max(point))
+        + Point::new(1, 1);
+    let cell = svg::cell_size(size.x, size.y);
+
+    let mut svg = Svg::new(size.x as f64 * cell, size.y as f64 * cell);
+    svg.rect(0.0, 0.0, size.x as f64 * cell, size.y as f64 * cell, "#223");
+    for Point { x, y } in points {
+        svg.rect(x as f64 * cell, y as f64 * cell, cell, cell, "#fd5");
+    }
+
+    svg.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = solve_part_one(&parse_input(INPUT));
+
+        #[cfg(debug_assertions)]
+        assert_eq!(result, 17);
+
+        #[cfg(not(debug_assertions))]
+        assert_eq!(result, 755);
+    }
+
+    #[bench]
+    fn bench_part_one(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_one(&input));
+    }
+
+    #[bench]
+    fn bench_part_two(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_two(&input));
+    }
+
+    #[test]
+    fn snapshot_sheets() {
+        let (mut points, folds) = parse_input(include_str!("../sample.TXT"));
+        let mut sheets = vec![dump_sheet(&points)];
+
+        for fold in folds {
+            points = fold_at(&points, fold);
+            sheets.push(dump_sheet(&points));
+        }
+
+        insta::assert_snapshot!("sheets", sheets.join("\n"));
+    }
+
+    #[test]
+    fn svg_sheet() {
+        let svg = svg_input(include_str!("../sample.TXT"));
+
+        // The 16 dots of the square and the background.
+        assert_eq!(svg.matches("<rect ").count(), 17);
+        assert!(svg.contains(r#"width="200" height="200""#));
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain_input(include_str!("../sample.TXT"));
+
+        assert_eq!(
+            explanation.text(),
+            "dots: 18\nfolds:\n  - y=7\n  - x=5\n\
+             part_one:\n  fold: y=7\n  dots: 17\n\
+             part_two:\n  dots: 16\n  sheet:\n    - ▓▓▓▓▓\n    - ▓░░░▓\n    - ▓░░░▓\n    - ▓░░░▓\n    - ▓▓▓▓▓\n"
+        );
+    }
+}
@@ -0,0 +1,240 @@
+#![feature(test)]
+
+use std::collections::HashMap;
+
+extern crate test;
+
+use common::{
+    arith::{self, Acc},
+    explain::Explanation,
+    parse::{self, pair, satisfy, section_break, tag, take_while1, Parser},
+};
+
+#[cfg(debug_assertions)]
+const INPUT: &str = include_str!("../sample.TXT");
+
+#[cfg(not(debug_assertions))]
+const INPUT: &str = include_str!("../input.TXT");
+
+type Rules = HashMap<u16, u8>;
+
+type Count = Acc<u64>;
+
+type FrequencyMap = HashMap<u16, Count>;
+
+type ParsedInput = (Vec<u8>, Rules);
+
+fn pack_char_bytes<T: IntoIterator<Item = u8>>(chars: T) -> u16 {
+    chars.into_iter().fold(0, |acc, ch| acc << 8 | (ch as u16))
+}
+
+fn unpack_chars(bytes: u16) -> (u8, u8) {
+    ((bytes >> 8) as u8, (bytes & 0xff) as u8)
+}
+
+fn gen_frequency(s: &[u8]) -> FrequencyMap {
+    let mut freq_map = HashMap::new();
+
+    for pair in s.windows(2) {
+        let pair = pack_char_bytes(pair.iter().copied());
+        let freq = freq_map.entry(pair).or_insert(0);
+        *freq += 1;
+    }
+
+    freq_map
+}
+
+fn replace(freq_map: &mut FrequencyMap, rules: &Rules) -> arith::Result<()> {
+    let copy = freq_map.clone();
+    for pair in copy.keys() {
+        if let Some(ch) = rules.get(pair) {
+            let (start, end) = unpack_chars(*pair);
+            let freq = *copy.get(pair).unwrap();
+            *freq_map.get_mut(pair).unwrap() -= freq;
+            for new_pair in [[start, *ch], [*ch, end]] {
+                let count = freq_map.entry(pack_char_bytes(new_pair)).or_insert(0);
+                *count = arith::add(*count, freq)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// How many times each element appears after `n_iter` steps, most common
+/// first.
+fn element_counts(
+    (template, rules): &ParsedInput,
+    n_iter: usize,
+) -> arith::Result<Vec<(char, Count)>> {
+    let mut freq_map = gen_frequency(template);
+
+    for _ in 0..n_iter {
+        replace(&mut freq_map, rules)?;
+    }
+
+    let mut count: [Count; 26] = [0; 26];
+
+    for (pair, frequency) in freq_map {
+        let (start, end) = unpack_chars(pair);
+        for ch in [start, end] {
+            let idx = (ch - b'A') as usize;
+            count[idx] = arith::add(count[idx], frequency)?;
+        }
+    }
+
+    count[(template[0] - b'A') as usize] += 1;
+    count[(template[template.len() - 1] - b'A') as usize] += 1;
+
+    // Every element but the ends is counted twice, once in each pair it is in.
+    let mut counts = (b'A'..=b'Z')
+        .zip(count)
+        .filter(|&(_, n)| n > 0)
+        .map(|(ch, n)| (ch as char, n / 2))
+        .collect::<Vec<_>>();
+
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    Ok(counts)
+}
+
+fn answer(input: &ParsedInput, n_iter: usize) -> arith::Result<Count> {
+    let counts = element_counts(input, n_iter)?;
+    let (max, min) = (counts[0].1, counts[counts.len() - 1].1);
+
+    Ok(max - min)
+}
+
+fn solve_part_one(input: &ParsedInput) -> arith::Result<Count> {
+    answer(input, 10)
+}
+
+fn solve_part_two(input: &ParsedInput) -> arith::Result<Count> {
+    answer(input, 40)
+}
+
+fn parse_input(input: &str) -> ParsedInput {
+    let is_element = |c: char| c.is_ascii_uppercase();
+    let polymer = take_while1(is_element, "polymer").map(|s| s.as_bytes().to_vec());
+    let element = || satisfy(is_element, "element").map(|c| c as u8);
+    let rule = pair(pair(element(), element()).skip(tag(" -> ")), element())
+        .map(|((start, end), ch)| (pack_char_bytes([start, end]), ch));
+    let manual = pair(polymer.skip(section_break()), parse::lines(rule));
+
+    let (initial_polymer, rules) = parse::parse(manual, input).unwrap_or_else(|e| panic!("{}", e));
+
+    (initial_polymer, rules.into_iter().collect())
+}
+
+pub fn solve() {
+    solve_input(INPUT);
+}
+
+pub fn solve_input(input: &str) {
+    let input = parse_input(input);
+
+    match solve_part_one(&input) {
+        Ok(result) => println!("Part #1: {}", result),
+        Err(e) => println!("Part #1: {}", e),
+    }
+
+    match solve_part_two(&input) {
+        Ok(result) => println!("Part #2: {}", result),
+        Err(e) => println!("Part #2: {}", e),
+    }
+}
+
+common::export_plugin!(2021, 14, solve_input);
+
+pub fn explain() -> Explanation {
+    explain_input(INPUT)
+}
+
+/// The most and least common elements after the steps of each part.
+pub fn explain_input(input: &str) -> Explanation {
+    let input = parse_input(input);
+    let mut explanation = Explanation::new();
+
+    for (part, n_iter) in [("part_one", 10), ("part_two", 40)] {
+        let mut steps = Explanation::new();
+        steps.field("steps", n_iter);
+
+        match element_counts(&input, n_iter) {
+            Ok(counts) => {
+                for (name, (element, count)) in [
+                    ("most_common", counts[0]),
+                    ("least_common", counts[counts.len() - 1]),
+                ] {
+                    let mut entry = Explanation::new();
+                    entry.field("element", element).field("count", count);
+                    steps.field(name, entry);
+                }
+            }
+            Err(e) => {
+                steps.field("error", e.to_string());
+            }
+        }
+
+        explanation.field(part, steps);
+    }
+
+    explanation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = solve_part_one(&parse_input(INPUT));
+
+        #[cfg(debug_assertions)]
+        assert_eq!(result, Ok(1588));
+
+        #[cfg(not(debug_assertions))]
+        assert_eq!(result, Ok(2509));
+    }
+
+    #[bench]
+    fn bench_part_one(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_one(&input));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = solve_part_two(&parse_input(INPUT));
+
+        #[cfg(debug_assertions)]
+        assert_eq!(result, Ok(2188189693529));
+
+        #[cfg(not(debug_assertions))]
+        assert_eq!(result, Ok(2827627697643));
+    }
+
+    #[bench]
+    fn bench_part_two(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_two(&input));
+    }
+
+    // Every step doubles the number of `NN` pairs.
+    #[cfg(feature = "checked")]
+    #[test]
+    fn test_overflow() {
+        let input = parse_input("NN\n\nNN -> N\n");
+
+        assert_eq!(answer(&input, 62), Ok(0));
+        assert_eq!(answer(&input, 130), Err(arith::Overflow));
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain_input(include_str!("../sample.TXT"));
+
+        assert_eq!(
+            explanation.json(),
+            r#"{"part_one":{"steps":10,"most_common":{"element":"B","count":1749},"least_common":{"element":"H","count":161}},"part_two":{"steps":40,"most_common":{"element":"B","count":2192039569602},"least_common":{"element":"H","count":3849876073}}}"#
+        );
+    }
+}
@@ -0,0 +1,228 @@
+#![feature(test)]
+extern crate test;
+
+use common::{
+    explain::Explanation,
+    geometry::Vec2,
+    grid::Grid,
+    search::{self, Search},
+    svg::{self, Svg},
+};
+
+#[cfg(debug_assertions)]
+const INPUT: &str = include_str!("../sample.TXT");
+
+#[cfg(not(debug_assertions))]
+const INPUT: &str = include_str!("../input.TXT");
+
+type ParsedInput<'a> = Grid<'a>;
+
+type Point = Vec2<usize>;
+
+/// Risk map tiled `factor` times in both directions, each tile one riskier
+/// than the one above or to its left. Risks are computed from the grid
+/// rather than stored for the whole map.
+#[derive(Debug, Clone, Copy)]
+struct Map<'a> {
+    grid: Grid<'a>,
+    factor: usize,
+}
+
+impl Map<'_> {
+    fn bounds(&self) -> Point {
+        Point::new(
+            self.grid.width() * self.factor,
+            self.grid.height() * self.factor,
+        )
+    }
+
+    fn risk(&self, p: Point) -> usize {
+        let (width, height) = (self.grid.width(), self.grid.height());
+        let risk = self.grid.digit(Point::new(p.x % width, p.y % height)) as usize;
+        ((risk + p.x / width + p.y / height - 1) % 9) + 1
+    }
+}
+
+// Dijkstra's algorithm, from the top-left to the bottom-right corner.
+fn search_map(map: Map) -> Search<Point, usize> {
+    let bounds = map.bounds();
+    let target = bounds - Point::new(1, 1);
+
+    let neighbours = |p: &Point| p.neighbours(bounds).map(|n| (n, map.risk(n)));
+
+    search::dijkstra(Point::default(), neighbours, |&p| p == target)
+}
+
+fn find_shortest_path(map: Map) -> Option<usize> {
+    search_map(map).goal_cost()
+}
+
+fn solve_part_one(input: &ParsedInput) -> usize {
+    find_shortest_path(Map {
+        grid: *input,
+        factor: 1,
+    })
+    .unwrap()
+}
+
+fn solve_part_two(input: &ParsedInput) -> usize {
+    let map = Map {
+        grid: *input,
+        factor: 5,
+    };
+    // dump_map(map, 10);
+    find_shortest_path(map).unwrap()
+}
+
+fn parse_input(input: &str) -> ParsedInput<'_> {
+    Grid::digits(input).unwrap_or_else(|e| panic!("{}", e))
+}
+
+#[allow(unused)]
+fn dump_map(map: Map, len: usize) {
+    let bounds = map.bounds();
+    for y in 0..bounds.y {
+        if y % len == 0 {
+            println!();
+        }
+        for x in 0..bounds.x {
+            if x % len == 0 {
+                print!(" ");
+            }
+            print!("{}", map.risk(Point::new(x, y)));
+        }
+        println!();
+    }
+}
+
+pub fn explain() -> Explanation {
+    explain_input(INPUT)
+}
+
+/// The size of each part's map, and the length and total risk of the
+/// lowest risk path across it.
+pub fn explain_input(input: &str) -> Explanation {
+    let grid = parse_input(input);
+
+    let mut explanation = Explanation::new();
+    for (part, factor) in [("part_one", 1), ("part_two", 5)] {
+        let map = Map { grid, factor };
+        let bounds = map.bounds();
+        let search = search_map(map);
+
+        let mut details = Explanation::new();
+        details.field("map", format!("{}x{}", bounds.x, bounds.y));
+        match (search.goal_path(), search.goal_cost()) {
+            (Some(path), Some(risk)) => details.field("steps", path.len() - 1).field("risk", risk),
+            _ => details.field("error", "no path to the bottom-right corner"),
+        };
+        explanation.field(part, details);
+    }
+    explanation
+}
+
+pub fn svg() -> String {
+    svg_input(INPUT)
+}
+
+/// The risk levels of the first part's map in shades of grey, with the
+/// lowest risk path over them.
+pub fn svg_input(input: &str) -> String {
+    let grid = parse_input(input);
+    let path = search_map(Map { grid, factor: 1 })
+        .goal_path()
+        .unwrap_or_default();
+
+    let cell = svg::cell_size(grid.width(), grid.height());
+    let mut svg = Svg::new(grid.width() as f64 * cell, grid.height() as f64 * cell);
+
+    for (y, row) in grid.rows().enumerate() {
+        for (x, &risk) in row.iter().enumerate() {
+            let fill = svg::grey((risk - b'0') as u32, 12);
+            svg.rect(x as f64 * cell, y as f64 * cell, cell, cell, &fill);
+        }
+    }
+
+    let path = path
+        .iter()
+        .map(|p| ((p.x as f64 + 0.5) * cell, (p.y as f64 + 0.5) * cell))
+        .collect::<Vec<_>>();
+    svg.polyline(&path, "#d22", (cell / 3.0).max(1.0));
+
+    svg.to_string()
+}
+
+pub fn solve() {
+    solve_input(INPUT);
+}
+
+pub fn solve_input(input: &str) {
+    let input = parse_input(input);
+
+    let result = solve_part_one(&input);
+    println!("Part #1: {}", result);
+
+    let result = solve_part_two(&input);
+    println!("Part #2: {}", result);
+}
+
+common::export_plugin!(2021, 15, solve_input);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = solve_part_one(&parse_input(INPUT));
+
+        #[cfg(debug_assertions)]
+        assert_eq!(result, 40);
+
+        #[cfg(not(debug_assertions))]
+        assert_eq!(result, 707);
+    }
+
+    #[bench]
+    fn bench_part_one(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_one(&input));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = solve_part_two(&parse_input(INPUT));
+
+        #[cfg(debug_assertions)]
+        assert_eq!(result, 315);
+
+        #[cfg(not(debug_assertions))]
+        assert_eq!(result, 2942);
+    }
+
+    #[bench]
+    fn bench_part_two(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_two(&input));
+    }
+
+    #[test]
+    fn svg_path() {
+        let svg = svg_input(include_str!("../sample.TXT"));
+
+        assert_eq!(svg.matches("<rect ").count(), 100);
+        assert!(svg.contains(r#"<polyline points="20,20 20,60 20,100 "#));
+        assert!(svg.contains(r#" 380,380" fill="none""#));
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain_input(include_str!("../sample.TXT"));
+
+        assert_eq!(
+            explanation.text(),
+            "part_one:\n  map: 10x10\n  steps: 18\n  risk: 40\n\
+             part_two:\n  map: 50x50\n  steps: 98\n  risk: 315\n"
+        );
+    }
+}
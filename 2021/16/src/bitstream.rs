@@ -7,7 +7,7 @@ pub struct BitStream<'a> {
 }
 
 impl<'a> BitStream<'a> {
-    pub fn new(data: &'a [u8], byte_len: u8) -> BitStream {
+    pub fn new(data: &'a [u8], byte_len: u8) -> BitStream<'a> {
         BitStream {
             data,
             current_byte: 0,
@@ -0,0 +1,450 @@
+#![feature(test)]
+extern crate test;
+
+mod bitstream;
+
+use bitstream::BitStream;
+use common::{
+    arith::{self, Acc},
+    explain::Explanation,
+};
+use std::fmt::Debug;
+
+#[cfg(debug_assertions)]
+const INPUT: &str = include_str!("../sample.TXT");
+
+#[cfg(not(debug_assertions))]
+const INPUT: &str = include_str!("../input.TXT");
+
+type ParsedInput = Vec<u8>;
+
+type Value = Acc<u64>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    UnexpectedEnd,
+    Overflow,
+}
+
+impl From<arith::Overflow> for Error {
+    fn from(_: arith::Overflow) -> Self {
+        Error::Overflow
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Packet {
+    Literal(LiteralPacket),
+    Operator(OperatorPacket),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LiteralPacket {
+    pub version: u8,
+    pub literal: Value,
+}
+
+#[derive(Debug, Clone)]
+pub struct OperatorPacket {
+    pub version: u8,
+    pub operator: u8,
+    pub sub_packets: Vec<Packet>,
+}
+
+impl Packet {
+    pub fn literal(version: u8, literal: Value) -> Self {
+        Packet::Literal(LiteralPacket { version, literal })
+    }
+
+    pub fn operator(version: u8, operator: u8, sub_packets: Vec<Packet>) -> Self {
+        Packet::Operator(OperatorPacket {
+            version,
+            operator,
+            sub_packets,
+        })
+    }
+
+    pub fn version(&self) -> u8 {
+        match self {
+            Packet::Literal(p) => p.version,
+            Packet::Operator(p) => p.version,
+        }
+    }
+
+    pub fn version_sum(&self) -> u64 {
+        match self {
+            Packet::Literal(_) => self.version() as u64,
+            Packet::Operator(p) => p
+                .sub_packets
+                .iter()
+                .fold(self.version() as u64, |acc, p| acc + p.version_sum()),
+        }
+    }
+
+    /// Number of packets in the tree, this one included.
+    pub fn count(&self) -> usize {
+        match self {
+            Packet::Literal(_) => 1,
+            Packet::Operator(p) => 1 + p.sub_packets.iter().map(Packet::count).sum::<usize>(),
+        }
+    }
+
+    fn parse(stream: &mut BitStream) -> Result<Packet, Error> {
+        let mut advance_by = |n| stream.advance_by(n).ok_or(Error::UnexpectedEnd);
+        let version = advance_by(3)? as u8;
+        let type_id = advance_by(3)? as u8;
+
+        match type_id {
+            4 => {
+                let mut result: Value = 0;
+                let mut keep;
+                loop {
+                    let mut literal = advance_by(5)?;
+                    let flag = 1 << 4;
+
+                    keep = ((literal & flag) >> 4) != 0;
+                    literal &= !flag;
+                    result = arith::shl(result, 4)? | arith::acc(literal);
+
+                    if !keep {
+                        break;
+                    }
+                }
+
+                Ok(Packet::literal(version, result))
+            }
+            _ => {
+                let len_tid = stream.advance().ok_or(Error::UnexpectedEnd)?;
+                let num_sz = (((len_tid == 0) as u8) << 2) | 0b1011;
+                let sp_len = stream.advance_by(num_sz).ok_or(Error::UnexpectedEnd)?;
+
+                let sub_packets = match len_tid {
+                    0 => {
+                        let mut sub_packets = vec![];
+                        let bits_read = stream.bits_read();
+                        while stream.bits_read() - bits_read < sp_len {
+                            sub_packets.push(Packet::parse(stream)?);
+                        }
+
+                        sub_packets
+                    }
+                    1 => (0..sp_len)
+                        .map(|_| Packet::parse(stream))
+                        .collect::<Result<Vec<_>, _>>()?,
+                    _ => panic!("invalid length type id"),
+                };
+
+                Ok(Packet::operator(version, type_id, sub_packets))
+            }
+        }
+    }
+
+    fn execute(&self) -> arith::Result<Value> {
+        match self {
+            Packet::Literal(p) => Ok(p.literal),
+            Packet::Operator(p) => {
+                let op = p.operator;
+                let sub_packets = p
+                    .sub_packets
+                    .iter()
+                    .map(|p| p.execute())
+                    .collect::<arith::Result<Vec<_>>>()?;
+
+                Ok(match op {
+                    0 => arith::sum(sub_packets)?,
+                    1 => arith::product(sub_packets)?,
+                    2 => sub_packets.into_iter().min().unwrap(),
+                    3 => sub_packets.into_iter().max().unwrap(),
+                    5..=7 => {
+                        let p = sub_packets;
+                        match op {
+                            5 => (p[0] > p[1]).into(),
+                            6 => (p[0] < p[1]).into(),
+                            7 => (p[0] == p[1]).into(),
+                            _ => unreachable!(),
+                        }
+                    }
+                    _ => panic!("invalid operator"),
+                })
+            }
+        }
+    }
+}
+
+fn op_to_str(op: u8) -> &'static str {
+    match op {
+        0 => "+",
+        1 => "*",
+        2 => "min",
+        3 => "max",
+        5 => ">",
+        6 => "<",
+        7 => "==",
+        _ => panic!("invalid operator"),
+    }
+}
+
+impl std::fmt::Display for Packet {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Packet::Literal(p) => write!(f, "{}", p.literal),
+            Packet::Operator(p) => {
+                let op = p.operator;
+                let sub_packets = p.sub_packets.iter().map(|p| p.to_string());
+
+                write!(f, "({}", op_to_str(op))?;
+                for sp in sub_packets {
+                    write!(f, " {}", sp)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+fn solve_part_one(input: &ParsedInput) -> Result<u64, Error> {
+    let mut stream = BitStream::new(input, 4);
+    let packet = Packet::parse(&mut stream)?;
+    Ok(packet.version_sum())
+}
+
+fn solve_part_two(input: &ParsedInput) -> Result<Value, Error> {
+    let mut stream = BitStream::new(input, 4);
+    let packet = Packet::parse(&mut stream)?;
+    Ok(packet.execute()?)
+}
+
+fn parse_input(input: &str) -> ParsedInput {
+    input
+        .trim()
+        .chars()
+        .fold(Vec::with_capacity(input.len()), |mut acc, el| {
+            acc.push(el.to_digit(16).unwrap() as u8);
+            acc
+        })
+}
+
+pub fn solve() {
+    solve_input(INPUT);
+}
+
+pub fn solve_input(input: &str) {
+    let input = parse_input(input);
+
+    match solve_part_one(&input) {
+        Ok(result) => println!("Part #1: {}", result),
+        Err(e) => println!("Part #1: {:?}", e),
+    }
+
+    match solve_part_two(&input) {
+        Ok(result) => println!("Part #2: {}", result),
+        Err(e) => println!("Part #2: {:?}", e),
+    }
+}
+
+common::export_plugin!(2021, 16, solve_input);
+
+pub fn explain() -> Explanation {
+    explain_input(INPUT)
+}
+
+/// The decoded packet as an expression, the sum of its versions and the
+/// value it evaluates to.
+pub fn explain_input(input: &str) -> Explanation {
+    let input = parse_input(input);
+    let mut explanation = Explanation::new();
+
+    let packet = match Packet::parse(&mut BitStream::new(&input, 4)) {
+        Ok(packet) => packet,
+        Err(e) => {
+            explanation.field("error", format!("{:?}", e));
+            return explanation;
+        }
+    };
+
+    let mut part_one = Explanation::new();
+    part_one.field("version_sum", packet.version_sum());
+
+    let mut part_two = Explanation::new();
+    match packet.execute() {
+        Ok(value) => part_two.field("value", value),
+        Err(e) => part_two.field("error", format!("{:?}", Error::from(e))),
+    };
+
+    explanation
+        .field("expression", packet.to_string())
+        .field("packets", packet.count())
+        .field("part_one", part_one)
+        .field("part_two", part_two);
+    explanation
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn parse_literal_packet() {
+        let input = "D2FE28";
+        let input = parse_input(input);
+        let mut stream = BitStream::new(&input, 4);
+        let packet = Packet::parse(&mut stream).unwrap();
+
+        assert_eq!(packet.version(), 6);
+        assert!(matches!(packet, Packet::Literal(p) if p.literal == 2021));
+    }
+
+    #[test]
+    fn parse_operator_tid_0() {
+        let input = "38006F45291200";
+
+        let input = parse_input(input);
+        let mut stream = BitStream::new(&input, 4);
+        let packet = Packet::parse(&mut stream).unwrap();
+
+        assert_eq!(packet.version(), 1);
+        assert!(matches!(packet, Packet::Operator(_)));
+    }
+
+    #[test]
+    fn test_part_one() {
+        let result = solve_part_one(&parse_input(INPUT));
+
+        #[cfg(debug_assertions)]
+        assert_eq!(result, Ok(31));
+
+        #[cfg(not(debug_assertions))]
+        assert_eq!(result, Ok(949));
+    }
+
+    #[bench]
+    fn bench_part_one(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_one(&input));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = solve_part_two(&parse_input(INPUT));
+
+        #[cfg(debug_assertions)]
+        assert_eq!(result, Ok(54));
+
+        #[cfg(not(debug_assertions))]
+        assert_eq!(result, Ok(1114600142730));
+    }
+
+    #[bench]
+    fn bench_part_two(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_two(&input));
+    }
+
+    // Bits of a version 0 literal packet holding `nibbles`.
+    fn literal_bits(nibbles: &[u8]) -> String {
+        let mut bits = String::from("000100");
+        for (idx, nibble) in nibbles.iter().enumerate() {
+            let keep = (idx + 1 < nibbles.len()) as u8;
+            bits.push_str(&format!("{}{:04b}", keep, nibble));
+        }
+        bits
+    }
+
+    fn to_hex(bits: &str) -> String {
+        let padding = "0".repeat((4 - bits.len() % 4) % 4);
+        (bits.to_owned() + &padding)
+            .as_bytes()
+            .chunks(4)
+            .map(|b| u8::from_str_radix(std::str::from_utf8(b).unwrap(), 2).unwrap())
+            .map(|n| format!("{:X}", n))
+            .collect()
+    }
+
+    fn parse_literal(nibbles: &[u8]) -> Result<Packet, Error> {
+        let input = parse_input(&to_hex(&literal_bits(nibbles)));
+        Packet::parse(&mut BitStream::new(&input, 4))
+    }
+
+    #[test]
+    fn parse_16_nibble_literal() {
+        let nibbles = [0xf, 1, 2, 3, 4, 5, 6, 7, 8, 9, 0xa, 0xb, 0xc, 0xd, 0xe, 0];
+        let packet = parse_literal(&nibbles).unwrap();
+
+        assert!(matches!(packet, Packet::Literal(p) if p.literal == 0xf123456789abcde0));
+    }
+
+    #[cfg(all(feature = "checked", not(feature = "wide")))]
+    #[test]
+    fn literal_past_16_nibbles_overflows() {
+        assert!(parse_literal(&[0xf; 16]).is_ok());
+        assert_eq!(parse_literal(&[0xf; 17]).unwrap_err(), Error::Overflow);
+    }
+
+    #[cfg(feature = "wide")]
+    #[test]
+    fn literal_past_16_nibbles_wide() {
+        let packet = parse_literal(&[0xf; 17]).unwrap();
+
+        assert!(matches!(packet, Packet::Literal(p) if p.literal == (1 << 68) - 1));
+    }
+
+    #[cfg(all(feature = "checked", feature = "wide"))]
+    #[test]
+    fn literal_past_32_nibbles_overflows() {
+        assert!(parse_literal(&[0xf; 32]).is_ok());
+        assert_eq!(parse_literal(&[0xf; 33]).unwrap_err(), Error::Overflow);
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    fn product_overflows() {
+        // Product of three 64-bit literals, too large even for 128 bits.
+        let mut bits = format!("001001{}{:011b}", 1, 3);
+        for _ in 0..3 {
+            bits.push_str(&literal_bits(&[0xf; 16]));
+        }
+
+        let input = parse_input(&to_hex(&bits));
+        assert_eq!(solve_part_two(&input), Err(Error::Overflow));
+    }
+
+    #[test]
+    fn snapshot_packets() {
+        let sample = include_str!("../sample.TXT");
+        let examples = [
+            sample.trim(),
+            "D2FE28",
+            "38006F45291200",
+            "EE00D40C823060",
+            "C200B40A82",
+            "04005AC33890",
+            "880086C3E88112",
+            "CE00C43D881120",
+            "D8005AC2A8F0",
+            "F600BC2D8F",
+            "9C005AC2F8F0",
+            "9C0141080250320F1802104A08",
+        ];
+
+        let packets = examples.map(|hex| {
+            let nibbles = parse_input(hex);
+            let packet = Packet::parse(&mut BitStream::new(&nibbles, 4)).unwrap();
+            format!("{} {}", hex, packet)
+        });
+
+        insta::assert_snapshot!("packets", packets.join("\n"));
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain_input("9C0141080250320F1802104A08");
+
+        assert_eq!(
+            explanation.text(),
+            "expression: (== (+ 1 3) (* 2 2))\npackets: 7\n\
+             part_one:\n  version_sum: 20\n\
+             part_two:\n  value: 1\n"
+        );
+    }
+}
@@ -0,0 +1,178 @@
+#![feature(test)]
+extern crate test;
+
+use common::{
+    explain::Explanation,
+    geometry::{Aabb, Vec2},
+    parse::{self, integer, pair, preceded, tag, Parser},
+};
+
+#[cfg(debug_assertions)]
+const INPUT: &str = include_str!("../sample.TXT");
+
+#[cfg(not(debug_assertions))]
+const INPUT: &str = include_str!("../input.TXT");
+
+type Pair = Vec2<i32>;
+
+type ParsedInput = Aabb<i32>;
+
+struct Probe {
+    pub p: Pair,
+    pub v: Pair,
+}
+
+impl Probe {
+    fn step(&mut self) {
+        self.p += self.v;
+        // Drag pulls x towards zero while gravity always pulls y down.
+        self.v -= Pair::new(self.v.x.signum(), 1);
+    }
+}
+
+fn simulate(area: &ParsedInput, v0: &Pair, max_y: &mut i32) -> bool {
+    let mut probe = Probe {
+        p: Pair::new(0, 0),
+        v: *v0,
+    };
+
+    while !area.contains(&probe.p) && probe.p.x <= area.max.x && probe.p.y >= area.min.y {
+        *max_y = (*max_y).max(probe.p.y);
+        probe.step();
+    }
+
+    area.contains(&probe.p)
+}
+
+/// Initial velocity of the hit that reaches the highest, and that height.
+fn highest_hit(area: &ParsedInput) -> Option<(Pair, i32)> {
+    let Aabb { min, max } = *area;
+    let mut best: Option<(Pair, i32)> = None;
+
+    for y in min.y..min.x * min.x {
+        for x in 1..max.x + 1 {
+            let pair = Pair::new(x, y);
+            let mut max_y = i32::MIN;
+            if simulate(area, &pair, &mut max_y) && best.is_none_or(|(_, best)| max_y > best) {
+                best = Some((pair, max_y));
+            }
+        }
+    }
+
+    best
+}
+
+fn solve_part_one(area: &ParsedInput) -> i32 {
+    highest_hit(area).map_or(i32::MIN, |(_, height)| height)
+}
+
+fn solve_part_two(area: &ParsedInput) -> usize {
+    let Aabb { min, max } = *area;
+    let mut answer = 0;
+
+    for y in min.y..=min.x * min.x {
+        for x in 1..max.x + 1 {
+            let pair = Pair::new(x, y);
+            if simulate(area, &pair, &mut 0) {
+                answer += 1;
+            }
+        }
+    }
+
+    answer
+}
+
+fn parse_input(input: &str) -> ParsedInput {
+    let range = || pair(integer().skip(tag("..")), integer());
+    let area = pair(
+        preceded(tag("target area: x="), range()),
+        preceded(tag(", y="), range()),
+    )
+    .map(|((x0, x1), (y0, y1))| Aabb::new(Pair::new(x0, y0), Pair::new(x1, y1)));
+
+    parse::parse(area, input).unwrap_or_else(|e| panic!("{}", e))
+}
+
+pub fn solve() {
+    solve_input(INPUT);
+}
+
+pub fn solve_input(input: &str) {
+    let input = parse_input(input);
+
+    let result = solve_part_one(&input);
+    println!("Part #1: {}", result);
+
+    let result = solve_part_two(&input);
+    println!("Part #2: {}", result);
+}
+
+common::export_plugin!(2021, 17, solve_input);
+
+pub fn explain() -> Explanation {
+    explain_input(INPUT)
+}
+
+/// The initial velocity that sends the probe highest while still hitting
+/// the target, and how many velocities hit it.
+pub fn explain_input(input: &str) -> Explanation {
+    let area = parse_input(input);
+    let mut explanation = Explanation::new();
+
+    if let Some((velocity, height)) = highest_hit(&area) {
+        explanation
+            .field("best_velocity", velocity.to_string())
+            .field("max_height", height);
+    }
+    explanation.field("hits", solve_part_two(&area));
+    explanation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = solve_part_one(&parse_input(INPUT));
+
+        #[cfg(debug_assertions)]
+        assert_eq!(result, 45);
+
+        #[cfg(not(debug_assertions))]
+        assert_eq!(result, 15931);
+    }
+
+    #[bench]
+    fn bench_part_one(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_one(&input));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = solve_part_two(&parse_input(INPUT));
+
+        #[cfg(debug_assertions)]
+        assert_eq!(result, 112);
+
+        #[cfg(not(debug_assertions))]
+        assert_eq!(result, 2555);
+    }
+
+    #[bench]
+    fn bench_part_two(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_two(&input));
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain_input(include_str!("../sample.TXT"));
+
+        assert_eq!(
+            explanation.text(),
+            "best_velocity: (6, 9)\nmax_height: 45\nhits: 112\n"
+        );
+    }
+}
@@ -0,0 +1,419 @@
+#![feature(test)]
+extern crate test;
+
+use std::{fmt::Display, ops::Add, str::FromStr};
+
+use common::{
+    explain::Explanation,
+    parse::{self, integer, pair, preceded, tag, Input, PResult, ParseError, Parser},
+};
+
+#[cfg(any(debug_assertions, not(puzzle_input)))]
+const INPUT: &str = include_str!("../sample.TXT");
+
+#[cfg(all(not(debug_assertions), puzzle_input))]
+const INPUT: &str = include_str!("../input.TXT");
+
+type ParsedInput = Vec<Number>;
+
+/// Pairs nested this deep explode.
+const MAX_DEPTH: usize = 4;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Number {
+    Regular(u32),
+    Pair(Box<Number>, Box<Number>),
+}
+
+impl Number {
+    fn pair(left: Number, right: Number) -> Self {
+        Number::Pair(Box::new(left), Box::new(right))
+    }
+
+    pub fn magnitude(&self) -> u32 {
+        match self {
+            Number::Regular(n) => *n,
+            Number::Pair(left, right) => 3 * left.magnitude() + 2 * right.magnitude(),
+        }
+    }
+
+    /// Explodes the leftmost pair nested too deep, returning the values it
+    /// leaves to add to the regular numbers on its left and right.
+    fn explode(&mut self, depth: usize) -> Option<(u32, u32)> {
+        let Number::Pair(left, right) = self else {
+            return None;
+        };
+
+        if depth >= MAX_DEPTH {
+            if let (Number::Regular(l), Number::Regular(r)) = (&**left, &**right) {
+                let carry = (*l, *r);
+                *self = Number::Regular(0);
+                return Some(carry);
+            }
+        }
+
+        if let Some((l, r)) = left.explode(depth + 1) {
+            right.add_leftmost(r);
+            return Some((l, 0));
+        }
+        if let Some((l, r)) = right.explode(depth + 1) {
+            left.add_rightmost(l);
+            return Some((0, r));
+        }
+        None
+    }
+
+    fn add_leftmost(&mut self, n: u32) {
+        match self {
+            Number::Regular(value) => *value += n,
+            Number::Pair(left, _) => left.add_leftmost(n),
+        }
+    }
+
+    fn add_rightmost(&mut self, n: u32) {
+        match self {
+            Number::Regular(value) => *value += n,
+            Number::Pair(_, right) => right.add_rightmost(n),
+        }
+    }
+
+    /// Splits the leftmost regular number of 10 or more.
+    fn split(&mut self) -> bool {
+        match self {
+            Number::Regular(n) if *n >= 10 => {
+                *self = Number::pair(Number::Regular(*n / 2), Number::Regular(n.div_ceil(2)));
+                true
+            }
+            Number::Regular(_) => false,
+            Number::Pair(left, right) => left.split() || right.split(),
+        }
+    }
+
+    /// Applies the first reduction action that can be, if any.
+    fn reduce_step(&mut self) -> bool {
+        self.explode(0).is_some() || self.split()
+    }
+
+    fn reduce(&mut self) {
+        while self.reduce_step() {}
+    }
+}
+
+impl Add for Number {
+    type Output = Number;
+
+    fn add(self, rhs: Number) -> Number {
+        let mut sum = Number::pair(self, rhs);
+        sum.reduce();
+        sum
+    }
+}
+
+impl Display for Number {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Number::Regular(n) => write!(f, "{}", n),
+            Number::Pair(left, right) => write!(f, "[{},{}]", left, right),
+        }
+    }
+}
+
+fn number(input: Input<'_>) -> PResult<'_, Number> {
+    if let Ok((n, rest)) = integer::<u32>().parse(input) {
+        return Ok((Number::Regular(n), rest));
+    }
+
+    pair(preceded(tag("["), number).skip(tag(",")), number)
+        .skip(tag("]"))
+        .map(|(left, right)| Number::pair(left, right))
+        .parse(input)
+}
+
+impl FromStr for Number {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse::parse(number, s)
+    }
+}
+
+fn sum(numbers: &[Number]) -> Option<Number> {
+    numbers.iter().cloned().reduce(|sum, n| sum + n)
+}
+
+fn solve_part_one(input: &ParsedInput) -> u32 {
+    sum(input).map_or(0, |sum| sum.magnitude())
+}
+
+/// The two different numbers whose sum has the largest magnitude, in the
+/// order they were added, and that sum.
+fn largest_pair(numbers: &[Number]) -> Option<(&Number, &Number, Number)> {
+    let mut largest: Option<(&Number, &Number, Number)> = None;
+
+    for (i, a) in numbers.iter().enumerate() {
+        for (j, b) in numbers.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            let sum = a.clone() + b.clone();
+            if largest
+                .as_ref()
+                .is_none_or(|(_, _, max)| sum.magnitude() > max.magnitude())
+            {
+                largest = Some((a, b, sum));
+            }
+        }
+    }
+
+    largest
+}
+
+fn solve_part_two(input: &ParsedInput) -> u32 {
+    largest_pair(input).map_or(0, |(_, _, sum)| sum.magnitude())
+}
+
+fn parse_input(input: &str) -> ParsedInput {
+    parse::parse(parse::lines(number), input).unwrap_or_else(|e| panic!("{}", e))
+}
+
+pub fn solve() {
+    solve_input(INPUT);
+}
+
+pub fn solve_input(input: &str) {
+    let input = parse_input(input);
+
+    let result = solve_part_one(&input);
+    println!("Part #1: {}", result);
+
+    let result = solve_part_two(&input);
+    println!("Part #2: {}", result);
+}
+
+common::export_plugin!(2021, 18, solve_input);
+
+pub fn explain() -> Explanation {
+    explain_input(INPUT)
+}
+
+/// The sum of the whole list, and the two numbers making the largest
+/// magnitude when added.
+pub fn explain_input(input: &str) -> Explanation {
+    let numbers = parse_input(input);
+
+    let mut part_one = Explanation::new();
+    part_one.field("numbers", numbers.len());
+    if let Some(sum) = sum(&numbers) {
+        part_one
+            .field("sum", sum.to_string())
+            .field("magnitude", sum.magnitude());
+    }
+
+    let mut part_two = Explanation::new();
+    if let Some((a, b, sum)) = largest_pair(&numbers) {
+        part_two
+            .field("left", a.to_string())
+            .field("right", b.to_string())
+            .field("sum", sum.to_string())
+            .field("magnitude", sum.magnitude());
+    }
+
+    let mut explanation = Explanation::new();
+    explanation
+        .field("part_one", part_one)
+        .field("part_two", part_two);
+    explanation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn num(s: &str) -> Number {
+        s.parse().unwrap()
+    }
+
+    fn numbers(lines: &[&str]) -> Vec<Number> {
+        lines.iter().map(|s| num(s)).collect()
+    }
+
+    // The answers for a fetched input aren't committed, so only the sample's
+    // are checked.
+    #[cfg(any(debug_assertions, not(puzzle_input)))]
+    #[test]
+    fn test_part_one() {
+        let result = solve_part_one(&parse_input(INPUT));
+
+        assert_eq!(result, 4140);
+    }
+
+    #[bench]
+    fn bench_part_one(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_one(&input));
+    }
+
+    #[cfg(any(debug_assertions, not(puzzle_input)))]
+    #[test]
+    fn test_part_two() {
+        let result = solve_part_two(&parse_input(INPUT));
+
+        assert_eq!(result, 3993);
+    }
+
+    #[bench]
+    fn bench_part_two(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_two(&input));
+    }
+
+    #[test]
+    fn parse_and_display() {
+        for s in [
+            "[1,2]",
+            "[[1,2],3]",
+            "[[[[1,3],[5,3]],[[1,3],[8,7]]],[[[4,9],[6,9]],[[8,2],[7,3]]]]",
+        ] {
+            assert_eq!(num(s).to_string(), s);
+        }
+        assert_eq!(
+            num("[9,[8,7]]"),
+            Number::pair(
+                Number::Regular(9),
+                Number::pair(Number::Regular(8), Number::Regular(7))
+            )
+        );
+
+        let err = "[1,2".parse::<Number>().unwrap_err();
+        assert_eq!((err.column, err.expected), (5, "]"));
+        assert!("[1;2]".parse::<Number>().is_err());
+    }
+
+    #[test]
+    fn explode() {
+        for (before, after) in [
+            ("[[[[[9,8],1],2],3],4]", "[[[[0,9],2],3],4]"),
+            ("[7,[6,[5,[4,[3,2]]]]]", "[7,[6,[5,[7,0]]]]"),
+            ("[[6,[5,[4,[3,2]]]],1]", "[[6,[5,[7,0]]],3]"),
+            (
+                "[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]",
+                "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]",
+            ),
+            (
+                "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]",
+                "[[3,[2,[8,0]]],[9,[5,[7,0]]]]",
+            ),
+        ] {
+            let mut n = num(before);
+            assert!(n.explode(0).is_some());
+            assert_eq!(n.to_string(), after);
+        }
+    }
+
+    #[test]
+    fn split() {
+        let mut n = num("[[[[0,7],4],[15,[0,13]]],[1,1]]");
+        assert!(n.split());
+        assert_eq!(n.to_string(), "[[[[0,7],4],[[7,8],[0,13]]],[1,1]]");
+        assert!(n.split());
+        assert_eq!(n.to_string(), "[[[[0,7],4],[[7,8],[0,[6,7]]]],[1,1]]");
+        assert!(!num("[9,[1,2]]").split());
+    }
+
+    #[test]
+    fn reduce_steps() {
+        let mut n = Number::pair(num("[[[[4,3],4],4],[7,[[8,4],9]]]"), num("[1,1]"));
+        let mut steps = vec![];
+        while n.reduce_step() {
+            steps.push(n.to_string());
+        }
+
+        assert_eq!(
+            steps,
+            [
+                "[[[[0,7],4],[7,[[8,4],9]]],[1,1]]",
+                "[[[[0,7],4],[15,[0,13]]],[1,1]]",
+                "[[[[0,7],4],[[7,8],[0,13]]],[1,1]]",
+                "[[[[0,7],4],[[7,8],[0,[6,7]]]],[1,1]]",
+                "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]",
+            ]
+        );
+        assert_eq!(
+            num("[[[[4,3],4],4],[7,[[8,4],9]]]") + num("[1,1]"),
+            num("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]")
+        );
+    }
+
+    #[test]
+    fn sums() {
+        let list = numbers(&["[1,1]", "[2,2]", "[3,3]", "[4,4]", "[5,5]", "[6,6]"]);
+        assert_eq!(
+            sum(&list[..4]).unwrap().to_string(),
+            "[[[[1,1],[2,2]],[3,3]],[4,4]]"
+        );
+        assert_eq!(
+            sum(&list[..5]).unwrap().to_string(),
+            "[[[[3,0],[5,3]],[4,4]],[5,5]]"
+        );
+        assert_eq!(
+            sum(&list).unwrap().to_string(),
+            "[[[[5,0],[7,4]],[5,5]],[6,6]]"
+        );
+
+        let list = numbers(&[
+            "[[[0,[4,5]],[0,0]],[[[4,5],[2,6]],[9,5]]]",
+            "[7,[[[3,7],[4,3]],[[6,3],[8,8]]]]",
+            "[[2,[[0,8],[3,4]]],[[[6,7],1],[7,[1,6]]]]",
+            "[[[[2,4],7],[6,[0,5]]],[[[6,8],[2,8]],[[2,1],[4,5]]]]",
+            "[7,[5,[[3,8],[1,4]]]]",
+            "[[2,[2,2]],[8,[8,1]]]",
+            "[2,9]",
+            "[1,[[[9,3],9],[[9,0],[0,7]]]]",
+            "[[[5,[7,4]],7],1]",
+            "[[[[4,2],2],6],[8,7]]",
+        ]);
+        assert_eq!(
+            sum(&list[..2]).unwrap().to_string(),
+            "[[[[4,0],[5,4]],[[7,7],[6,0]]],[[8,[7,7]],[[7,9],[5,0]]]]"
+        );
+        assert_eq!(
+            sum(&list).unwrap().to_string(),
+            "[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]"
+        );
+
+        assert_eq!(
+            sum(&parse_input(include_str!("../sample.TXT")))
+                .unwrap()
+                .to_string(),
+            "[[[[6,6],[7,6]],[[7,7],[7,0]]],[[[7,7],[7,7]],[[7,8],[9,9]]]]"
+        );
+    }
+
+    #[test]
+    fn magnitudes() {
+        for (n, magnitude) in [
+            ("[[1,2],[[3,4],5]]", 143),
+            ("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]", 1384),
+            ("[[[[1,1],[2,2]],[3,3]],[4,4]]", 445),
+            ("[[[[3,0],[5,3]],[4,4]],[5,5]]", 791),
+            ("[[[[5,0],[7,4]],[5,5]],[6,6]]", 1137),
+            (
+                "[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]",
+                3488,
+            ),
+        ] {
+            assert_eq!(num(n).magnitude(), magnitude);
+        }
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain_input("[1,1]\n[2,2]\n[3,3]\n[4,4]\n");
+
+        assert_eq!(
+            explanation.text(),
+            "part_one:\n  numbers: 4\n  sum: [[[[1,1],[2,2]],[3,3]],[4,4]]\n  magnitude: 445\n\
+             part_two:\n  left: [4,4]\n  right: [3,3]\n  sum: [[4,4],[3,3]]\n  magnitude: 90\n"
+        );
+    }
+}
@@ -0,0 +1,319 @@
+#![feature(test)]
+extern crate test;
+
+use std::collections::{HashMap, HashSet};
+
+use common::{
+    explain::Explanation,
+    geometry::Vec3,
+    parse::{self, integer, line_break, lines, pair, preceded, tag, vec3, Parser},
+};
+
+#[cfg(any(debug_assertions, not(puzzle_input)))]
+const INPUT: &str = include_str!("../sample.TXT");
+
+#[cfg(all(not(debug_assertions), puzzle_input))]
+const INPUT: &str = include_str!("../input.TXT");
+
+type Point = Vec3<i32>;
+
+/// Beacons seen by each scanner, relative to it.
+type ParsedInput = Vec<Vec<Point>>;
+
+/// Beacons two scanners must agree on to be considered overlapping.
+const MIN_OVERLAP: usize = 12;
+
+/// Rotation by quarter turns: component `i` of a rotated point is component
+/// `axes[i]` of the original, times `signs[i]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Rotation {
+    axes: [usize; 3],
+    signs: [i32; 3],
+}
+
+impl Rotation {
+    /// The 24 orientations a scanner can face, the identity first: each
+    /// permutation of the axes with the sign flips that keep it a rotation
+    /// rather than a reflection.
+    fn all() -> Vec<Rotation> {
+        // Permutations along with their parity.
+        const PERMUTATIONS: [([usize; 3], i32); 6] = [
+            ([0, 1, 2], 1),
+            ([1, 2, 0], 1),
+            ([2, 0, 1], 1),
+            ([0, 2, 1], -1),
+            ([2, 1, 0], -1),
+            ([1, 0, 2], -1),
+        ];
+
+        let mut rotations = Vec::with_capacity(24);
+        for (axes, parity) in PERMUTATIONS {
+            for flips in 0..8 {
+                let signs = [0, 1, 2].map(|i| if flips >> i & 1 == 1 { -1 } else { 1 });
+                if parity * signs.iter().product::<i32>() == 1 {
+                    rotations.push(Rotation { axes, signs });
+                }
+            }
+        }
+
+        rotations
+    }
+
+    fn apply(&self, p: Point) -> Point {
+        let components: [i32; 3] = p.into();
+        Point::from([0, 1, 2].map(|i| components[self.axes[i]] * self.signs[i]))
+    }
+}
+
+/// Scanners and beacons, relative to the first scanner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Map {
+    scanners: Vec<Point>,
+    beacons: HashSet<Point>,
+}
+
+/// Finds the orientation and position at which `report` shares at least
+/// [`MIN_OVERLAP`] beacons with `known`, returning the scanner's position
+/// and its beacons moved there.
+fn align(known: &[Point], report: &[Point], rotations: &[Rotation]) -> Option<(Point, Vec<Point>)> {
+    for rotation in rotations {
+        let rotated = report
+            .iter()
+            .map(|&b| rotation.apply(b))
+            .collect::<Vec<_>>();
+        let mut offsets = HashMap::new();
+
+        for &a in known {
+            for &b in &rotated {
+                let count = offsets.entry(a - b).or_insert(0);
+                *count += 1;
+
+                if *count >= MIN_OVERLAP {
+                    let offset = a - b;
+                    return Some((offset, rotated.iter().map(|&b| b + offset).collect()));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+// Aligns the scanners one overlap at a time, starting from the first one.
+fn assemble(reports: &[Vec<Point>]) -> Map {
+    let rotations = Rotation::all();
+    let mut placed: Vec<Option<(Point, Vec<Point>)>> = vec![None; reports.len()];
+    placed[0] = Some((Point::default(), reports[0].clone()));
+    let mut frontier = vec![0];
+
+    while let Some(i) = frontier.pop() {
+        let known = placed[i].as_ref().unwrap().1.clone();
+
+        for j in 0..reports.len() {
+            if placed[j].is_none() {
+                if let Some(aligned) = align(&known, &reports[j], &rotations) {
+                    placed[j] = Some(aligned);
+                    frontier.push(j);
+                }
+            }
+        }
+    }
+
+    let mut map = Map {
+        scanners: vec![],
+        beacons: HashSet::new(),
+    };
+    for (i, scanner) in placed.into_iter().enumerate() {
+        let (position, beacons) =
+            scanner.unwrap_or_else(|| panic!("Scanner {} overlaps no other scanner", i));
+        map.scanners.push(position);
+        map.beacons.extend(beacons);
+    }
+
+    map
+}
+
+fn solve_part_one(input: &ParsedInput) -> usize {
+    assemble(input).beacons.len()
+}
+
+/// The indices of the two scanners furthest apart, and their Manhattan
+/// distance.
+fn furthest_apart(scanners: &[Point]) -> (usize, usize, i32) {
+    let mut largest = (0, 0, 0);
+    for (i, a) in scanners.iter().enumerate() {
+        for (j, b) in scanners.iter().enumerate().skip(i + 1) {
+            let distance = a.manhattan_to(*b);
+            if distance > largest.2 {
+                largest = (i, j, distance);
+            }
+        }
+    }
+
+    largest
+}
+
+fn solve_part_two(input: &ParsedInput) -> i32 {
+    furthest_apart(&assemble(input).scanners).2
+}
+
+fn parse_input(input: &str) -> ParsedInput {
+    let header = pair(tag("--- scanner "), integer::<usize>())
+        .skip(tag(" ---"))
+        .skip(line_break());
+    let scanner = preceded(header, lines(vec3()));
+
+    parse::parse(parse::sections(scanner), input).unwrap_or_else(|e| panic!("{}", e))
+}
+
+pub fn solve() {
+    solve_input(INPUT);
+}
+
+pub fn solve_input(input: &str) {
+    let input = parse_input(input);
+
+    let result = solve_part_one(&input);
+    println!("Part #1: {}", result);
+
+    let result = solve_part_two(&input);
+    println!("Part #2: {}", result);
+}
+
+common::export_plugin!(2021, 19, solve_input);
+
+pub fn explain() -> Explanation {
+    explain_input(INPUT)
+}
+
+/// Where every scanner turned out to be relative to the first one, how many
+/// beacons they see together and which two are furthest apart.
+pub fn explain_input(input: &str) -> Explanation {
+    let reports = parse_input(input);
+    let map = assemble(&reports);
+    let (a, b, distance) = furthest_apart(&map.scanners);
+
+    let mut part_one = Explanation::new();
+    part_one.field("beacons", map.beacons.len());
+
+    let mut part_two = Explanation::new();
+    part_two
+        .field("scanners", format!("{} and {}", a, b))
+        .field("distance", distance);
+
+    let mut explanation = Explanation::new();
+    explanation
+        .field(
+            "scanners",
+            map.scanners
+                .iter()
+                .enumerate()
+                .map(|(i, position)| format!("{}: {} beacons at {}", i, reports[i].len(), position))
+                .collect::<Vec<_>>(),
+        )
+        .field("part_one", part_one)
+        .field("part_two", part_two);
+    explanation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The answers for a fetched input aren't committed, so only the sample's
+    // are checked.
+    #[cfg(any(debug_assertions, not(puzzle_input)))]
+    #[test]
+    fn test_part_one() {
+        let result = solve_part_one(&parse_input(INPUT));
+
+        assert_eq!(result, 79);
+    }
+
+    #[bench]
+    fn bench_part_one(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_one(&input));
+    }
+
+    #[cfg(any(debug_assertions, not(puzzle_input)))]
+    #[test]
+    fn test_part_two() {
+        let result = solve_part_two(&parse_input(INPUT));
+
+        assert_eq!(result, 3621);
+    }
+
+    #[bench]
+    fn bench_part_two(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_two(&input));
+    }
+
+    #[test]
+    fn rotations() {
+        let rotations = Rotation::all();
+        let p = Point::new(1, 2, 3);
+
+        assert_eq!(rotations.len(), 24);
+        assert_eq!(rotations[0].apply(p), p);
+        assert_eq!(
+            rotations
+                .iter()
+                .map(|r| r.apply(p))
+                .collect::<HashSet<_>>()
+                .len(),
+            24
+        );
+
+        // The same scanner, in some of its orientations.
+        let orientations = parse_input(
+            "--- scanner 0 ---\n-1,-1,1\n-2,-2,2\n-3,-3,3\n-2,-3,1\n5,6,-4\n8,0,7\n\n\
+             --- scanner 0 ---\n1,-1,1\n2,-2,2\n3,-3,3\n2,-1,3\n-5,4,-6\n-8,-7,0\n\n\
+             --- scanner 0 ---\n-1,-1,-1\n-2,-2,-2\n-3,-3,-3\n-1,-3,-2\n4,6,5\n-7,0,8\n\n\
+             --- scanner 0 ---\n1,1,-1\n2,2,-2\n3,3,-3\n1,3,-2\n-4,-6,5\n7,0,8\n\n\
+             --- scanner 0 ---\n1,1,1\n2,2,2\n3,3,3\n3,1,2\n-6,-4,-5\n0,7,-8\n",
+        );
+        for orientation in &orientations[1..] {
+            assert!(rotations.iter().any(|r| orientations[0]
+                .iter()
+                .map(|&b| r.apply(b))
+                .eq(orientation.iter().copied())));
+        }
+    }
+
+    #[test]
+    fn scanner_positions() {
+        let map = assemble(&parse_input(include_str!("../sample.TXT")));
+
+        assert_eq!(
+            map.scanners,
+            [
+                Point::new(0, 0, 0),
+                Point::new(68, -1246, -43),
+                Point::new(1105, -1205, 1229),
+                Point::new(-92, -2380, -20),
+                Point::new(-20, -1133, 1061),
+            ]
+        );
+        assert!(map.beacons.contains(&Point::new(-618, -824, -621)));
+        assert!(map.beacons.contains(&Point::new(459, -707, 401)));
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain_input(include_str!("../sample.TXT"));
+
+        assert_eq!(
+            explanation.text(),
+            "scanners:\n  \
+             - 0: 25 beacons at (0, 0, 0)\n  \
+             - 1: 25 beacons at (68, -1246, -43)\n  \
+             - 2: 26 beacons at (1105, -1205, 1229)\n  \
+             - 3: 25 beacons at (-92, -2380, -20)\n  \
+             - 4: 26 beacons at (-20, -1133, 1061)\n\
+             part_one:\n  beacons: 79\n\
+             part_two:\n  scanners: 2 and 3\n  distance: 3621\n"
+        );
+    }
+}
@@ -0,0 +1,331 @@
+#![feature(test)]
+extern crate test;
+
+use std::fmt::Display;
+
+use common::{
+    explain::Explanation,
+    parse::{self, lines, pair, section_break, take_while1, Parser},
+    svg::{self, Svg},
+};
+
+#[cfg(any(debug_assertions, not(puzzle_input)))]
+const INPUT: &str = include_str!("../sample.TXT");
+
+#[cfg(all(not(debug_assertions), puzzle_input))]
+const INPUT: &str = include_str!("../input.TXT");
+
+type ParsedInput = (Algorithm, Image);
+
+const PASSES_PART_ONE: usize = 2;
+const PASSES_PART_TWO: usize = 50;
+
+/// Output pixel for each 9-bit neighbourhood, read row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Algorithm(Vec<bool>);
+
+/// Finite window of an infinite image, every pixel outside of which has the
+/// background colour.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<bool>,
+    background: bool,
+}
+
+impl Image {
+    fn get(&self, x: isize, y: isize) -> bool {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return self.background;
+        }
+        self.pixels[y as usize * self.width + x as usize]
+    }
+
+    /// Pixels of column `x` in rows `y - 1` to `y + 1`, each at the bit it
+    /// takes in a neighbourhood index as the rightmost column.
+    fn column(&self, x: isize, y: isize) -> usize {
+        (self.get(x, y - 1) as usize) << 6
+            | (self.get(x, y) as usize) << 3
+            | self.get(x, y + 1) as usize
+    }
+
+    /// The image after one pass, one pixel larger on every side. The
+    /// background turns into what the algorithm makes of a neighbourhood of
+    /// only background, so it flips on every pass when bit 0 is set and bit
+    /// 511 isn't.
+    fn enhance(&self, algorithm: &Algorithm) -> Image {
+        let (width, height) = (self.width + 2, self.height + 2);
+        let mut pixels = Vec::with_capacity(width * height);
+
+        for y in 0..height as isize {
+            // Output pixel `x` is centred on source column `x - 1`, so the
+            // window slides one column per pixel, starting at `-2..=0`.
+            let y = y - 1;
+            let step = |index: usize, x| ((index << 1) & 0b110_110_110) | self.column(x, y);
+            let mut index = step(step(0, -2), -1);
+
+            for x in 0..width as isize {
+                index = step(index, x);
+                pixels.push(algorithm.0[index]);
+            }
+        }
+
+        Image {
+            width,
+            height,
+            pixels,
+            background: algorithm.0[if self.background { 511 } else { 0 }],
+        }
+    }
+
+    /// Number of lit pixels, unless the infinite background is lit.
+    fn lit(&self) -> Option<usize> {
+        (!self.background).then(|| self.pixels.iter().filter(|&&p| p).count())
+    }
+
+    fn svg(&self) -> Svg {
+        let colour = |lit| if lit { "#222" } else { "#eee" };
+        let cell = svg::cell_size(self.width, self.height);
+        let (width, height) = (self.width as f64 * cell, self.height as f64 * cell);
+
+        let mut svg = Svg::new(width, height);
+        svg.rect(0.0, 0.0, width, height, colour(self.background));
+        for (i, &lit) in self.pixels.iter().enumerate() {
+            if lit != self.background {
+                let (x, y) = (i % self.width, i / self.width);
+                svg.rect(x as f64 * cell, y as f64 * cell, cell, cell, colour(lit));
+            }
+        }
+
+        svg
+    }
+}
+
+impl Display for Image {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for row in self.pixels.chunks(self.width) {
+            let row = row
+                .iter()
+                .map(|&lit| if lit { '#' } else { '.' })
+                .collect::<String>();
+            writeln!(f, "{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+fn enhance(input: &ParsedInput, passes: usize) -> Image {
+    let (algorithm, image) = input;
+    (0..passes).fold(image.clone(), |image, _| image.enhance(algorithm))
+}
+
+fn lit_after(input: &ParsedInput, passes: usize) -> usize {
+    enhance(input, passes)
+        .lit()
+        .expect("Infinitely many pixels are lit")
+}
+
+fn solve_part_one(input: &ParsedInput) -> usize {
+    lit_after(input, PASSES_PART_ONE)
+}
+
+fn solve_part_two(input: &ParsedInput) -> usize {
+    lit_after(input, PASSES_PART_TWO)
+}
+
+fn parse_input(input: &str) -> ParsedInput {
+    let pixels = || {
+        take_while1(|c| c == '#' || c == '.', "pixel")
+            .map(|s: &str| s.bytes().map(|b| b == b'#').collect::<Vec<_>>())
+    };
+    let (algorithm, rows) =
+        parse::parse(pair(pixels().skip(section_break()), lines(pixels())), input)
+            .unwrap_or_else(|e| panic!("{}", e));
+
+    assert_eq!(algorithm.len(), 512, "The algorithm must have 512 pixels");
+    let width = rows[0].len();
+    assert!(
+        rows.iter().all(|row| row.len() == width),
+        "Image rows must have the same width"
+    );
+
+    let image = Image {
+        width,
+        height: rows.len(),
+        pixels: rows.concat(),
+        background: false,
+    };
+    (Algorithm(algorithm), image)
+}
+
+pub fn solve() {
+    solve_input(INPUT);
+}
+
+pub fn solve_input(input: &str) {
+    let input = parse_input(input);
+
+    let result = solve_part_one(&input);
+    println!("Part #1: {}", result);
+
+    let result = solve_part_two(&input);
+    println!("Part #2: {}", result);
+}
+
+common::export_plugin!(2021, 20, solve_input);
+
+pub fn explain() -> Explanation {
+    explain_input(INPUT)
+}
+
+/// The size of the image before and after each part's passes, and whether
+/// the infinite background flips between dark and lit.
+pub fn explain_input(input: &str) -> Explanation {
+    let input = parse_input(input);
+    let (algorithm, image) = &input;
+
+    let mut explanation = Explanation::new();
+    explanation
+        .field("image", format!("{}x{}", image.width, image.height))
+        .field(
+            "background",
+            match (algorithm.0[0], algorithm.0[511]) {
+                (true, false) => "flips on every pass",
+                (true, true) => "lit after the first pass",
+                (false, _) => "stays dark",
+            },
+        );
+
+    for (part, passes) in [("part_one", PASSES_PART_ONE), ("part_two", PASSES_PART_TWO)] {
+        let image = enhance(&input, passes);
+
+        let mut details = Explanation::new();
+        details
+            .field("passes", passes)
+            .field("image", format!("{}x{}", image.width, image.height));
+        match image.lit() {
+            Some(lit) => details.field("lit", lit),
+            None => details.field("error", "infinitely many pixels are lit"),
+        };
+        explanation.field(part, details);
+    }
+
+    explanation
+}
+
+pub fn svg() -> String {
+    svg_input(INPUT)
+}
+
+/// The image after the first part's passes.
+pub fn svg_input(input: &str) -> String {
+    enhance(&parse_input(input), PASSES_PART_ONE)
+        .svg()
+        .to_string()
+}
+
+pub fn frames() -> Vec<Svg> {
+    frames_input(INPUT)
+}
+
+/// The image before any pass and after each pass of the second part.
+pub fn frames_input(input: &str) -> Vec<Svg> {
+    let (algorithm, image) = parse_input(input);
+
+    let mut frames = vec![image.svg()];
+    let mut image = image;
+    for _ in 0..PASSES_PART_TWO {
+        image = image.enhance(&algorithm);
+        frames.push(image.svg());
+    }
+    frames
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The answers for a fetched input aren't committed, so only the sample's
+    // are checked.
+    #[cfg(any(debug_assertions, not(puzzle_input)))]
+    #[test]
+    fn test_part_one() {
+        let result = solve_part_one(&parse_input(INPUT));
+
+        assert_eq!(result, 35);
+    }
+
+    #[bench]
+    fn bench_part_one(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_one(&input));
+    }
+
+    #[cfg(any(debug_assertions, not(puzzle_input)))]
+    #[test]
+    fn test_part_two() {
+        let result = solve_part_two(&parse_input(INPUT));
+
+        assert_eq!(result, 3351);
+    }
+
+    #[bench]
+    fn bench_part_two(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_two(&input));
+    }
+
+    #[test]
+    fn passes() {
+        let input = parse_input(include_str!("../sample.TXT"));
+
+        assert_eq!(
+            enhance(&input, 1).to_string(),
+            ".##.##.\n#..#.#.\n##.#..#\n####..#\n.#..##.\n..##..#\n...#.#.\n"
+        );
+        assert_eq!(enhance(&input, 2).to_string().lines().count(), 9);
+    }
+
+    #[test]
+    fn flipping_background() {
+        // Lights up pixels with only dark neighbours and darkens all others.
+        let mut algorithm = vec![false; 512];
+        algorithm[0] = true;
+        let input = (
+            Algorithm(algorithm),
+            parse_input(include_str!("../sample.TXT")).1,
+        );
+
+        let once = enhance(&input, 1);
+        assert!(once.background);
+        assert_eq!(once.lit(), None);
+        assert!(once.get(-5, 100));
+
+        let twice = enhance(&input, 2);
+        assert!(!twice.background);
+        assert!(twice.lit().is_some());
+    }
+
+    #[test]
+    fn drawings() {
+        let frames = frames_input(include_str!("../sample.TXT"));
+
+        assert_eq!(frames.len(), PASSES_PART_TWO + 1);
+        // The background, then the 10 lit pixels of the sample.
+        assert_eq!(frames[0].to_string().matches("<rect ").count(), 11);
+        assert!(svg_input(include_str!("../sample.TXT")).starts_with("<svg"));
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain_input(include_str!("../sample.TXT"));
+
+        assert_eq!(
+            explanation.text(),
+            "image: 5x5\nbackground: stays dark\n\
+             part_one:\n  passes: 2\n  image: 9x9\n  lit: 35\n\
+             part_two:\n  passes: 50\n  image: 105x105\n  lit: 3351\n"
+        );
+    }
+}
@@ -0,0 +1,304 @@
+#![feature(test)]
+extern crate test;
+
+use std::collections::HashMap;
+
+use common::{
+    explain::Explanation,
+    parse::{self, integer, lines, pair, preceded, tag},
+};
+
+#[cfg(any(debug_assertions, not(puzzle_input)))]
+const INPUT: &str = include_str!("../sample.TXT");
+
+#[cfg(all(not(debug_assertions), puzzle_input))]
+const INPUT: &str = include_str!("../input.TXT");
+
+/// Starting spaces of both players, from 1.
+type ParsedInput = [u32; 2];
+
+/// Board and goal of a game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    /// Number of spaces around the track.
+    pub board: u32,
+    /// Score that wins the game.
+    pub target: u32,
+}
+
+impl Rules {
+    pub const PRACTICE: Rules = Rules {
+        board: 10,
+        target: 1000,
+    };
+
+    pub const DIRAC: Rules = Rules {
+        board: 10,
+        target: 21,
+    };
+}
+
+/// Sums of three rolls of a three-sided die, with how many of the 27
+/// universes they split into have each.
+const DIRAC_ROLLS: [(u32, u128); 7] = [(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)];
+
+/// Players of a game, the one about to move first. Positions count from 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct State {
+    positions: [u32; 2],
+    scores: [u32; 2],
+}
+
+impl State {
+    fn new(start: [u32; 2], rules: Rules) -> Self {
+        for position in start {
+            assert!(
+                (1..=rules.board).contains(&position),
+                "Position {} is off the board",
+                position
+            );
+        }
+
+        State {
+            positions: start.map(|p| p - 1),
+            scores: [0; 2],
+        }
+    }
+
+    /// Moves the current player by `roll`, then hands over to the other one.
+    fn play(self, roll: u32, rules: Rules) -> Self {
+        let position = (self.positions[0] + roll) % rules.board;
+        State {
+            positions: [self.positions[1], position],
+            scores: [self.scores[1], self.scores[0] + position + 1],
+        }
+    }
+
+    /// Score of the player who moved last.
+    fn last_score(&self) -> u32 {
+        self.scores[1]
+    }
+}
+
+/// Outcome of a game with the practice die.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Practice {
+    /// Index of the player who won.
+    winner: usize,
+    losing_score: u32,
+    rolls: u32,
+}
+
+fn play_practice(start: [u32; 2], rules: Rules) -> Practice {
+    let mut state = State::new(start, rules);
+    let mut die = (1..=100).cycle();
+    let mut rolls = 0;
+
+    loop {
+        let roll = die.by_ref().take(3).sum::<u32>();
+        rolls += 3;
+        state = state.play(roll, rules);
+
+        if state.last_score() >= rules.target {
+            // The loser is now the player about to move, and the players
+            // swap places on every turn.
+            return Practice {
+                winner: (rolls as usize / 3 - 1) % 2,
+                losing_score: state.scores[0],
+                rolls,
+            };
+        }
+    }
+}
+
+/// Plays with a die rolling 1 to 100 in order, returning the loser's score
+/// times the number of rolls.
+pub fn practice_game(start: [u32; 2], rules: Rules) -> u32 {
+    let game = play_practice(start, rules);
+    game.losing_score * game.rolls
+}
+
+/// Number of universes in which each player wins with the Dirac die.
+pub fn dirac_wins(start: [u32; 2], rules: Rules) -> [u128; 2] {
+    fn wins(state: State, rules: Rules, memo: &mut HashMap<State, [u128; 2]>) -> [u128; 2] {
+        if let Some(&wins) = memo.get(&state) {
+            return wins;
+        }
+
+        let mut total = [0; 2];
+        for (roll, universes) in DIRAC_ROLLS {
+            let next = state.play(roll, rules);
+            if next.last_score() >= rules.target {
+                total[0] += universes;
+            } else {
+                // The other player moves first in `next`.
+                let [theirs, ours] = wins(next, rules, memo);
+                total[0] += universes * ours;
+                total[1] += universes * theirs;
+            }
+        }
+
+        memo.insert(state, total);
+        total
+    }
+
+    wins(State::new(start, rules), rules, &mut HashMap::new())
+}
+
+fn solve_part_one(input: &ParsedInput) -> u32 {
+    practice_game(*input, Rules::PRACTICE)
+}
+
+fn solve_part_two(input: &ParsedInput) -> u128 {
+    let [first, second] = dirac_wins(*input, Rules::DIRAC);
+    first.max(second)
+}
+
+fn parse_input(input: &str) -> ParsedInput {
+    let player = preceded(
+        pair(tag("Player "), integer::<u8>()),
+        preceded(tag(" starting position: "), integer()),
+    );
+    let players = parse::parse(lines(player), input).unwrap_or_else(|e| panic!("{}", e));
+
+    players
+        .try_into()
+        .unwrap_or_else(|players: Vec<_>| panic!("Expected 2 players, found {}", players.len()))
+}
+
+pub fn solve() {
+    solve_input(INPUT);
+}
+
+pub fn solve_input(input: &str) {
+    let input = parse_input(input);
+
+    let result = solve_part_one(&input);
+    println!("Part #1: {}", result);
+
+    let result = solve_part_two(&input);
+    println!("Part #2: {}", result);
+}
+
+common::export_plugin!(2021, 21, solve_input);
+
+pub fn explain() -> Explanation {
+    explain_input(INPUT)
+}
+
+/// Who wins the practice game and after how many rolls, and in how many
+/// universes each player wins with the Dirac die.
+pub fn explain_input(input: &str) -> Explanation {
+    let start = parse_input(input);
+
+    let game = play_practice(start, Rules::PRACTICE);
+    let mut part_one = Explanation::new();
+    part_one
+        .field("winner", format!("player {}", game.winner + 1))
+        .field("losing_score", game.losing_score)
+        .field("rolls", game.rolls);
+
+    let wins = dirac_wins(start, Rules::DIRAC);
+    let mut part_two = Explanation::new();
+    part_two
+        .field("player_1_wins", wins[0])
+        .field("player_2_wins", wins[1]);
+
+    let mut explanation = Explanation::new();
+    explanation
+        .field("part_one", part_one)
+        .field("part_two", part_two);
+    explanation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The answers for a fetched input aren't committed, so only the sample's
+    // are checked.
+    #[cfg(any(debug_assertions, not(puzzle_input)))]
+    #[test]
+    fn test_part_one() {
+        let result = solve_part_one(&parse_input(INPUT));
+
+        assert_eq!(result, 739785);
+    }
+
+    #[bench]
+    fn bench_part_one(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_one(&input));
+    }
+
+    #[cfg(any(debug_assertions, not(puzzle_input)))]
+    #[test]
+    fn test_part_two() {
+        let result = solve_part_two(&parse_input(INPUT));
+
+        assert_eq!(result, 444356092776315);
+    }
+
+    #[bench]
+    fn bench_part_two(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_two(&input));
+    }
+
+    #[test]
+    fn dirac_universes() {
+        assert_eq!(
+            dirac_wins([4, 8], Rules::DIRAC),
+            [444356092776315, 341960390180808]
+        );
+
+        // Every universe ends after the first player's first move.
+        let quick = Rules {
+            board: 10,
+            target: 1,
+        };
+        assert_eq!(dirac_wins([4, 8], quick), [27, 0]);
+
+        // Longer games split into more universes than 64 bits can count.
+        let long = Rules {
+            board: 10,
+            target: 40,
+        };
+        assert!(dirac_wins([4, 8], long)[0] > u64::MAX as u128);
+    }
+
+    #[test]
+    fn custom_rules() {
+        let short = Rules {
+            board: 10,
+            target: 20,
+        };
+        // Player 1 scores 10, 4 and 6 to win on the 15th roll, while
+        // player 2 has scored 3 and 6.
+        assert_eq!(practice_game([4, 8], short), 15 * (3 + 6));
+
+        let small = Rules {
+            board: 4,
+            target: 21,
+        };
+        let [first, second] = dirac_wins([1, 4], small);
+        assert!(first > 0 && second > 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Position 11 is off the board")]
+    fn off_the_board() {
+        practice_game([11, 1], Rules::PRACTICE);
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain_input(include_str!("../sample.TXT"));
+
+        assert_eq!(
+            explanation.text(),
+            "part_one:\n  winner: player 1\n  losing_score: 745\n  rolls: 993\n\
+             part_two:\n  player_1_wins: 444356092776315\n  player_2_wins: 341960390180808\n"
+        );
+    }
+}
@@ -0,0 +1,335 @@
+#![feature(test)]
+extern crate test;
+
+use std::collections::HashMap;
+
+use common::{
+    explain::Explanation,
+    geometry::Vec3,
+    parse::{self, integer, lines, pair, preceded, tag, Input, PResult, Parser},
+};
+
+#[cfg(any(debug_assertions, not(puzzle_input)))]
+const INPUT: &str = include_str!("../sample.TXT");
+
+#[cfg(all(not(debug_assertions), puzzle_input))]
+const INPUT: &str = include_str!("../input.TXT");
+
+type Point = Vec3<i64>;
+
+type ParsedInput = Vec<Step>;
+
+/// Region of the reactor the initialisation procedure is limited to.
+const INIT_REGION: Cuboid = Cuboid::new(Point::new(-50, -50, -50), Point::new(50, 50, 50));
+
+/// Axis-aligned box of cubes, inclusive on both corners.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cuboid {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Cuboid {
+    pub const fn new(min: Point, max: Point) -> Self {
+        Cuboid { min, max }
+    }
+
+    /// Number of cubes inside.
+    pub fn volume(&self) -> i64 {
+        let size = self.max - self.min + Point::new(1, 1, 1);
+        size.x * size.y * size.z
+    }
+
+    pub fn intersection(&self, other: &Cuboid) -> Option<Cuboid> {
+        let min = self.min.max(other.min);
+        let max = self.max.min(other.max);
+
+        (min.x <= max.x && min.y <= max.y && min.z <= max.z).then_some(Cuboid::new(min, max))
+    }
+
+    /// The cubes of `self` outside of `other`, as up to six disjoint cuboids.
+    pub fn subtract(&self, other: &Cuboid) -> Vec<Cuboid> {
+        let Some(cut) = self.intersection(other) else {
+            return vec![*self];
+        };
+
+        let (mut min, mut max): ([i64; 3], [i64; 3]) = (self.min.into(), self.max.into());
+        let (cut_min, cut_max): ([i64; 3], [i64; 3]) = (cut.min.into(), cut.max.into());
+        let mut pieces = Vec::new();
+
+        // Slices off the slabs on both sides of the cut, one axis at a time,
+        // shrinking what is left to the cut's extent on that axis.
+        for axis in 0..3 {
+            if min[axis] < cut_min[axis] {
+                let mut below = max;
+                below[axis] = cut_min[axis] - 1;
+                pieces.push(Cuboid::new(min.into(), below.into()));
+            }
+            if cut_max[axis] < max[axis] {
+                let mut above = min;
+                above[axis] = cut_max[axis] + 1;
+                pieces.push(Cuboid::new(above.into(), max.into()));
+            }
+            min[axis] = cut_min[axis];
+            max[axis] = cut_max[axis];
+        }
+
+        pieces
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub on: bool,
+    pub cuboid: Cuboid,
+}
+
+/// Number of cubes left on after `steps`.
+///
+/// Keeps the cuboids seen so far with signed counts, such that the lit volume
+/// is the sum of their volumes times their counts. A step cancels out its
+/// intersection with every counted cuboid, then counts itself if it turns
+/// cubes on.
+pub fn lit_volume(steps: impl IntoIterator<Item = Step>) -> i64 {
+    let mut counts: HashMap<Cuboid, i64> = HashMap::new();
+
+    for step in steps {
+        let mut changes: HashMap<Cuboid, i64> = HashMap::new();
+        for (cuboid, &count) in &counts {
+            if let Some(overlap) = cuboid.intersection(&step.cuboid) {
+                *changes.entry(overlap).or_default() -= count;
+            }
+        }
+        if step.on {
+            *changes.entry(step.cuboid).or_default() += 1;
+        }
+
+        for (cuboid, change) in changes {
+            *counts.entry(cuboid).or_default() += change;
+        }
+        counts.retain(|_, count| *count != 0);
+    }
+
+    counts
+        .iter()
+        .map(|(cuboid, count)| cuboid.volume() * count)
+        .sum()
+}
+
+/// Disjoint cuboids of the cubes left on after `steps`, each step carving
+/// itself out of the lit ones.
+pub fn lit_cuboids(steps: impl IntoIterator<Item = Step>) -> Vec<Cuboid> {
+    let mut lit: Vec<Cuboid> = Vec::new();
+
+    for step in steps {
+        lit = lit.iter().flat_map(|c| c.subtract(&step.cuboid)).collect();
+        if step.on {
+            lit.push(step.cuboid);
+        }
+    }
+
+    lit
+}
+
+/// The steps, clipped to the initialisation region.
+fn init_steps(steps: &[Step]) -> impl Iterator<Item = Step> + '_ {
+    steps.iter().filter_map(|step| {
+        let cuboid = step.cuboid.intersection(&INIT_REGION)?;
+        Some(Step { cuboid, ..*step })
+    })
+}
+
+fn solve_part_one(input: &ParsedInput) -> i64 {
+    lit_volume(init_steps(input))
+}
+
+fn solve_part_two(input: &ParsedInput) -> i64 {
+    lit_volume(input.iter().copied())
+}
+
+fn switch(input: Input<'_>) -> PResult<'_, bool> {
+    match tag("on ").parse(input) {
+        Ok((_, rest)) => Ok((true, rest)),
+        Err(_) => tag("off ").map(|_| false).parse(input),
+    }
+}
+
+fn parse_input(input: &str) -> ParsedInput {
+    let range = || pair(integer::<i64>().skip(tag("..")), integer::<i64>());
+    let cuboid = pair(
+        pair(preceded(tag("x="), range()), preceded(tag(",y="), range())),
+        preceded(tag(",z="), range()),
+    )
+    .map(|((x, y), z)| Cuboid::new(Point::new(x.0, y.0, z.0), Point::new(x.1, y.1, z.1)));
+    let step = pair(switch, cuboid).map(|(on, cuboid)| Step { on, cuboid });
+
+    parse::parse(lines(step), input).unwrap_or_else(|e| panic!("{}", e))
+}
+
+pub fn solve() {
+    solve_input(INPUT);
+}
+
+pub fn solve_input(input: &str) {
+    let input = parse_input(input);
+
+    let result = solve_part_one(&input);
+    println!("Part #1: {}", result);
+
+    let result = solve_part_two(&input);
+    println!("Part #2: {}", result);
+}
+
+common::export_plugin!(2021, 22, solve_input);
+
+pub fn explain() -> Explanation {
+    explain_input(INPUT)
+}
+
+/// How many steps each part follows, and the cubes left on as disjoint
+/// cuboids.
+pub fn explain_input(input: &str) -> Explanation {
+    let steps = parse_input(input);
+
+    let mut explanation = Explanation::new();
+    explanation
+        .field("on_steps", steps.iter().filter(|step| step.on).count())
+        .field("off_steps", steps.iter().filter(|step| !step.on).count());
+
+    let parts: [(&str, Vec<Step>); 2] = [
+        ("part_one", init_steps(&steps).collect()),
+        ("part_two", steps.clone()),
+    ];
+    for (part, steps) in parts {
+        let lit = lit_cuboids(steps.iter().copied());
+
+        let mut details = Explanation::new();
+        details
+            .field("steps", steps.len())
+            .field("lit_cuboids", lit.len())
+            .field("cubes", lit.iter().map(Cuboid::volume).sum::<i64>());
+        explanation.field(part, details);
+    }
+
+    explanation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cuboid(min: (i64, i64, i64), max: (i64, i64, i64)) -> Cuboid {
+        Cuboid::new(
+            Point::new(min.0, min.1, min.2),
+            Point::new(max.0, max.1, max.2),
+        )
+    }
+
+    // The answers for a fetched input aren't committed, so only the sample's
+    // are checked.
+    #[cfg(any(debug_assertions, not(puzzle_input)))]
+    #[test]
+    fn test_part_one() {
+        let result = solve_part_one(&parse_input(INPUT));
+
+        assert_eq!(result, 590784);
+    }
+
+    #[bench]
+    fn bench_part_one(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_one(&input));
+    }
+
+    #[cfg(any(debug_assertions, not(puzzle_input)))]
+    #[test]
+    fn test_part_two() {
+        let result = solve_part_two(&parse_input(INPUT));
+
+        // The puzzle only answers the first part for this sample, see
+        // `larger_example` for one with both answers.
+        assert_eq!(result, 39769202357779);
+    }
+
+    #[bench]
+    fn bench_part_two(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_two(&input));
+    }
+
+    #[test]
+    fn cuboids() {
+        let a = cuboid((0, 0, 0), (3, 3, 3));
+        let b = cuboid((2, 2, 2), (5, 5, 5));
+
+        assert_eq!(a.volume(), 64);
+        assert_eq!(a.intersection(&b), Some(cuboid((2, 2, 2), (3, 3, 3))));
+        assert_eq!(a.intersection(&cuboid((4, 0, 0), (5, 3, 3))), None);
+
+        let pieces = a.subtract(&b);
+        assert_eq!(pieces.iter().map(Cuboid::volume).sum::<i64>(), 64 - 8);
+        for (i, p) in pieces.iter().enumerate() {
+            assert_eq!(p.intersection(&b), None);
+            for q in &pieces[i + 1..] {
+                assert_eq!(p.intersection(q), None);
+            }
+        }
+
+        // A hole in the middle leaves all six slabs around it.
+        assert_eq!(a.subtract(&cuboid((1, 1, 1), (2, 2, 2))).len(), 6);
+        assert_eq!(a.subtract(&cuboid((9, 9, 9), (9, 9, 9))), [a]);
+        assert_eq!(a.subtract(&cuboid((-1, -1, -1), (4, 4, 4))), []);
+    }
+
+    #[test]
+    fn small_reboot() {
+        let steps = parse_input(
+            "on x=10..12,y=10..12,z=10..12\n\
+             on x=11..13,y=11..13,z=11..13\n\
+             off x=9..11,y=9..11,z=9..11\n\
+             on x=10..10,y=10..10,z=10..10\n",
+        );
+
+        let lit = steps
+            .iter()
+            .scan(Vec::new(), |done, &step| {
+                done.push(step);
+                Some(lit_volume(done.clone()))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(lit, [27, 46, 38, 39]);
+    }
+
+    #[test]
+    fn larger_example() {
+        let input = parse_input(include_str!("../larger.TXT"));
+
+        assert_eq!(solve_part_one(&input), 474140);
+        assert_eq!(solve_part_two(&input), 2758514936282235);
+    }
+
+    #[test]
+    fn signed_volumes_match_disjoint_cuboids() {
+        let steps = parse_input(include_str!("../sample.TXT"));
+
+        for steps in [init_steps(&steps).collect::<Vec<_>>(), steps.clone()] {
+            let disjoint = lit_cuboids(steps.iter().copied());
+            assert_eq!(
+                disjoint.iter().map(Cuboid::volume).sum::<i64>(),
+                lit_volume(steps)
+            );
+        }
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain_input(include_str!("../sample.TXT"));
+
+        assert_eq!(
+            explanation.text(),
+            "on_steps: 17\noff_steps: 5\n\
+             part_one:\n  steps: 20\n  lit_cuboids: 145\n  cubes: 590784\n\
+             part_two:\n  steps: 22\n  lit_cuboids: 147\n  cubes: 39769202357779\n"
+        );
+    }
+}
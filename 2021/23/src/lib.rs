@@ -0,0 +1,443 @@
+#![feature(test)]
+extern crate test;
+
+use std::fmt::Display;
+
+use common::{explain::Explanation, search};
+
+#[cfg(any(debug_assertions, not(puzzle_input)))]
+const INPUT: &str = include_str!("../sample.TXT");
+
+#[cfg(all(not(debug_assertions), puzzle_input))]
+const INPUT: &str = include_str!("../input.TXT");
+
+type ParsedInput = Burrow;
+
+const HALLWAY: usize = 11;
+const ROOMS: usize = 4;
+const MAX_DEPTH: usize = 4;
+/// Hallway spaces right outside each room, where amphipods never stop.
+const DOORS: [usize; ROOMS] = [2, 4, 6, 8];
+/// Hallway spaces amphipods can stop at.
+const STOPS: [usize; 7] = [0, 1, 3, 5, 7, 9, 10];
+/// Energy per step of amphipods A to D.
+const ENERGY: [u32; ROOMS] = [1, 10, 100, 1000];
+const EMPTY: u8 = b'.';
+
+/// Rows the folded part of the diagram hides between the first and second
+/// rows of the rooms.
+const UNFOLDED: [[u8; ROOMS]; 2] = [*b"DCBA", *b"DBAC"];
+
+/// Where every amphipod is, as the letters of the diagram. Rooms are
+/// listed from the hallway down, and only their first `depth` spaces are
+/// used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Burrow {
+    hallway: [u8; HALLWAY],
+    rooms: [[u8; MAX_DEPTH]; ROOMS],
+    depth: usize,
+}
+
+/// Index of the room an amphipod belongs in, and of its energy.
+fn kind(amphipod: u8) -> usize {
+    (amphipod - b'A') as usize
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Space {
+    Hallway(usize),
+    Room(usize, usize),
+}
+
+impl Display for Space {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Space::Hallway(x) => write!(f, "hallway {}", x + 1),
+            Space::Room(room, _) => write!(f, "room {}", room + 1),
+        }
+    }
+}
+
+impl Burrow {
+    fn get(&self, space: Space) -> u8 {
+        match space {
+            Space::Hallway(x) => self.hallway[x],
+            Space::Room(room, y) => self.rooms[room][y],
+        }
+    }
+
+    fn spaces(&self) -> impl Iterator<Item = Space> + '_ {
+        let rooms =
+            (0..ROOMS).flat_map(move |room| (0..self.depth).map(move |y| Space::Room(room, y)));
+        (0..HALLWAY).map(Space::Hallway).chain(rooms)
+    }
+
+    /// Moves the amphipod at `from` to `to`, returning the burrow after and
+    /// the energy it took.
+    fn step(&self, from: Space, to: Space) -> (Burrow, u32) {
+        let x = |space| match space {
+            Space::Hallway(x) => (x, 0),
+            Space::Room(room, y) => (DOORS[room], y + 1),
+        };
+        let ((from_x, from_y), (to_x, to_y)) = (x(from), x(to));
+        let steps = from_x.abs_diff(to_x) + from_y + to_y;
+
+        let amphipod = self.get(from);
+        let mut next = *self;
+        for (space, value) in [(from, EMPTY), (to, amphipod)] {
+            match space {
+                Space::Hallway(x) => next.hallway[x] = value,
+                Space::Room(room, y) => next.rooms[room][y] = value,
+            }
+        }
+
+        (next, steps as u32 * ENERGY[kind(amphipod)])
+    }
+
+    /// Whether the hallway is empty between `from` and `to`, `to` included.
+    fn clear(&self, from: usize, to: usize) -> bool {
+        let range = if from < to {
+            from + 1..=to
+        } else {
+            to..=from - 1
+        };
+        self.hallway[range].iter().all(|&c| c == EMPTY)
+    }
+
+    /// Whether the room only holds amphipods that belong there, if any.
+    fn ready(&self, room: usize) -> bool {
+        self.rooms[room][..self.depth]
+            .iter()
+            .all(|&c| c == EMPTY || kind(c) == room)
+    }
+
+    fn is_organized(&self) -> bool {
+        self.hallway.iter().all(|&c| c == EMPTY) && (0..ROOMS).all(|room| self.ready(room))
+    }
+
+    /// Burrows one move away, with the energy of the move.
+    fn moves(&self) -> Vec<(Burrow, u32)> {
+        // Going home as soon as possible is never worse, so that is the only
+        // move considered when there is one.
+        for x in 0..HALLWAY {
+            let amphipod = self.hallway[x];
+            if amphipod == EMPTY {
+                continue;
+            }
+
+            let room = kind(amphipod);
+            if self.ready(room) && self.clear(x, DOORS[room]) {
+                let y = self.rooms[room][..self.depth]
+                    .iter()
+                    .rposition(|&c| c == EMPTY)
+                    .unwrap();
+                return vec![self.step(Space::Hallway(x), Space::Room(room, y))];
+            }
+        }
+
+        let mut moves = Vec::new();
+        for room in (0..ROOMS).filter(|&room| !self.ready(room)) {
+            let y = self.rooms[room].iter().position(|&c| c != EMPTY).unwrap();
+            for x in STOPS {
+                if self.clear(DOORS[room], x) {
+                    moves.push(self.step(Space::Room(room, y), Space::Hallway(x)));
+                }
+            }
+        }
+
+        moves
+    }
+
+    /// Energy left to spend if every misplaced amphipod could walk straight
+    /// through the others into its room.
+    fn estimate(&self) -> u32 {
+        self.spaces()
+            .filter_map(|space| {
+                let amphipod = self.get(space);
+                if amphipod == EMPTY {
+                    return None;
+                }
+
+                let home = DOORS[kind(amphipod)];
+                let steps = match space {
+                    Space::Hallway(x) => x.abs_diff(home) + 1,
+                    Space::Room(room, _) if room == kind(amphipod) => return None,
+                    Space::Room(room, y) => y + 1 + DOORS[room].abs_diff(home) + 1,
+                };
+                Some(steps as u32 * ENERGY[kind(amphipod)])
+            })
+            .sum()
+    }
+
+    /// The burrow with the folded rows of the diagram in its rooms.
+    fn unfold(&self) -> Burrow {
+        assert_eq!(self.depth, 2, "Only two rows deep burrows are folded");
+
+        let mut unfolded = *self;
+        for (i, room) in unfolded.rooms.iter_mut().enumerate() {
+            *room = [room[0], UNFOLDED[0][i], UNFOLDED[1][i], room[1]];
+        }
+        unfolded.depth = 4;
+        unfolded
+    }
+
+    /// Which amphipod moved between two burrows, from where to where.
+    fn moved(&self, next: &Burrow) -> (u8, Space, Space) {
+        let from = self
+            .spaces()
+            .find(|&s| self.get(s) != EMPTY && next.get(s) == EMPTY)
+            .unwrap();
+        let to = self
+            .spaces()
+            .find(|&s| self.get(s) == EMPTY && next.get(s) != EMPTY)
+            .unwrap();
+        (self.get(from), from, to)
+    }
+}
+
+impl Display for Burrow {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let row = |cells: [u8; ROOMS]| {
+            cells
+                .iter()
+                .map(|&c| (c as char).to_string())
+                .collect::<Vec<_>>()
+                .join("#")
+        };
+
+        writeln!(f, "{}", "#".repeat(HALLWAY + 2))?;
+        writeln!(f, "#{}#", String::from_utf8_lossy(&self.hallway))?;
+        for y in 0..self.depth {
+            let cells = row(self.rooms.map(|room| room[y]));
+            if y == 0 {
+                writeln!(f, "###{}###", cells)?;
+            } else {
+                writeln!(f, "  #{}#", cells)?;
+            }
+        }
+        writeln!(f, "  {}", "#".repeat(2 * ROOMS + 1))
+    }
+}
+
+/// Cheapest way to organise the amphipods, as the burrows along the way with
+/// the energy spent to reach them.
+fn organize(burrow: &Burrow) -> Option<Vec<(Burrow, u32)>> {
+    let search = search::astar(
+        *burrow,
+        Burrow::moves,
+        Burrow::estimate,
+        Burrow::is_organized,
+    );
+
+    let path = search.goal_path()?;
+    Some(
+        path.into_iter()
+            .map(|burrow| (burrow, search.costs[&burrow]))
+            .collect(),
+    )
+}
+
+fn least_energy(burrow: &Burrow) -> u32 {
+    let path = organize(burrow).expect("The amphipods can't be organized");
+    path.last().unwrap().1
+}
+
+fn solve_part_one(input: &ParsedInput) -> u32 {
+    least_energy(input)
+}
+
+fn solve_part_two(input: &ParsedInput) -> u32 {
+    least_energy(&input.unfold())
+}
+
+fn parse_input(input: &str) -> ParsedInput {
+    let mut lines = input.lines().skip(1);
+    let cell = |&c: &u8| c == EMPTY || c.is_ascii_uppercase() && c <= b'D';
+
+    let hallway = lines.next().expect("Missing hallway").as_bytes();
+    let hallway: [u8; HALLWAY] = hallway
+        .iter()
+        .copied()
+        .filter(cell)
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap_or_else(|_| panic!("The hallway must have {} spaces", HALLWAY));
+
+    let mut burrow = Burrow {
+        hallway,
+        rooms: [[EMPTY; MAX_DEPTH]; ROOMS],
+        depth: 0,
+    };
+    for line in lines {
+        let cells = line.bytes().filter(cell).collect::<Vec<_>>();
+        if cells.is_empty() {
+            break;
+        }
+
+        assert_eq!(cells.len(), ROOMS, "Invalid room row {:?}", line);
+        assert!(
+            burrow.depth < MAX_DEPTH,
+            "Rooms are at most {} deep",
+            MAX_DEPTH
+        );
+        for (room, &c) in cells.iter().enumerate() {
+            burrow.rooms[room][burrow.depth] = c;
+        }
+        burrow.depth += 1;
+    }
+
+    burrow
+}
+
+pub fn solve() {
+    solve_input(INPUT);
+}
+
+pub fn solve_input(input: &str) {
+    let input = parse_input(input);
+
+    let result = solve_part_one(&input);
+    println!("Part #1: {}", result);
+
+    let result = solve_part_two(&input);
+    println!("Part #2: {}", result);
+}
+
+common::export_plugin!(2021, 23, solve_input);
+
+pub fn explain() -> Explanation {
+    explain_input(INPUT)
+}
+
+fn explain_moves(burrow: &Burrow) -> Explanation {
+    let mut explanation = Explanation::new();
+    let Some(path) = organize(burrow) else {
+        explanation.field("error", "The amphipods can't be organized");
+        return explanation;
+    };
+
+    let moves = path
+        .windows(2)
+        .map(|pair| {
+            let [(before, spent), (after, total)] = [pair[0], pair[1]];
+            let (amphipod, from, to) = before.moved(&after);
+            format!(
+                "{} from {} to {}: {}",
+                amphipod as char,
+                from,
+                to,
+                total - spent
+            )
+        })
+        .collect::<Vec<_>>();
+
+    explanation
+        .field("energy", path.last().unwrap().1)
+        .field("moves", moves);
+    explanation
+}
+
+/// The cheapest sequence of moves for each part.
+pub fn explain_input(input: &str) -> Explanation {
+    let burrow = parse_input(input);
+
+    let mut explanation = Explanation::new();
+    explanation
+        .field("part_one", explain_moves(&burrow))
+        .field("part_two", explain_moves(&burrow.unfold()));
+    explanation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The answers for a fetched input aren't committed, so only the sample's
+    // are checked.
+    #[cfg(any(debug_assertions, not(puzzle_input)))]
+    #[test]
+    fn test_part_one() {
+        let result = solve_part_one(&parse_input(INPUT));
+
+        assert_eq!(result, 12521);
+    }
+
+    #[bench]
+    fn bench_part_one(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_one(&input));
+    }
+
+    #[cfg(any(debug_assertions, not(puzzle_input)))]
+    #[test]
+    fn test_part_two() {
+        let result = solve_part_two(&parse_input(INPUT));
+
+        assert_eq!(result, 44169);
+    }
+
+    #[bench]
+    fn bench_part_two(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_two(&input));
+    }
+
+    #[test]
+    fn diagram() {
+        let sample = include_str!("../sample.TXT");
+        let burrow = parse_input(sample);
+
+        assert_eq!(burrow.to_string(), sample);
+        assert_eq!(
+            burrow.unfold().to_string(),
+            "#############\n\
+             #...........#\n\
+             ###B#C#B#D###\n  \
+             #D#C#B#A#\n  \
+             #D#B#A#C#\n  \
+             #A#D#C#A#\n  \
+             #########\n"
+        );
+        assert_eq!(parse_input(&burrow.unfold().to_string()), burrow.unfold());
+    }
+
+    #[test]
+    fn moves() {
+        let burrow = parse_input(include_str!("../sample.TXT"));
+
+        // The top amphipod of each room can go to any of the 7 stops.
+        assert_eq!(burrow.moves().len(), 4 * 7);
+        let (after, energy) = burrow.step(Space::Room(2, 0), Space::Hallway(3));
+        assert_eq!(energy, 40);
+        assert_eq!(after.hallway, *b"...B.......");
+
+        // Room 3 now only holds a C, which belongs there, while B's room
+        // still holds a C and a D.
+        assert!(after.ready(2));
+        assert!(!after.ready(1));
+        // The B in the hallway keeps the first room from its right-hand
+        // stops and the others from the stops left of it.
+        assert_eq!(after.moves().len(), 2 + 4 + 4);
+
+        let organized =
+            parse_input("#############\n#...........#\n###A#B#C#D###\n  #A#B#C#D#\n  #########\n");
+        assert!(organized.is_organized());
+        assert_eq!(organized.estimate(), 0);
+        assert!(!burrow.is_organized());
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain_moves(&parse_input(include_str!("../sample.TXT")));
+        let text = explanation.text();
+        let energy = text
+            .lines()
+            .filter_map(|line| line.rsplit_once(": "))
+            .filter(|(name, _)| name.contains(" from "))
+            .map(|(_, energy)| energy.parse::<u32>().unwrap())
+            .sum::<u32>();
+
+        assert!(text.starts_with("energy: 12521\nmoves:\n"));
+        assert_eq!(energy, 12521);
+    }
+}
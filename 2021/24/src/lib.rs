@@ -0,0 +1,652 @@
+#![feature(test)]
+extern crate test;
+
+use std::{collections::HashSet, fmt::Display};
+
+use common::{
+    explain::Explanation,
+    parse::{self, integer, lines, satisfy, tag, word, Input, PResult, Parser},
+};
+
+#[cfg(any(debug_assertions, not(puzzle_input)))]
+const INPUT: &str = include_str!("../sample.TXT");
+
+#[cfg(all(not(debug_assertions), puzzle_input))]
+const INPUT: &str = include_str!("../input.TXT");
+
+type ParsedInput = Vec<Instruction>;
+
+/// Number of digits in a model number.
+const DIGITS: usize = 14;
+
+/// Digits to try for each position, from the one looked for first.
+const LARGEST: [i64; 9] = [9, 8, 7, 6, 5, 4, 3, 2, 1];
+const SMALLEST: [i64; 9] = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Register {
+    W,
+    X,
+    Y,
+    Z,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    Register(Register),
+    Number(i64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Inp(Register),
+    Add(Register, Operand),
+    Mul(Register, Operand),
+    Div(Register, Operand),
+    Mod(Register, Operand),
+    Eql(Register, Operand),
+}
+
+impl Display for Register {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let name = match self {
+            Register::W => 'w',
+            Register::X => 'x',
+            Register::Y => 'y',
+            Register::Z => 'z',
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl Display for Operand {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Operand::Register(r) => write!(f, "{}", r),
+            Operand::Number(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let (name, a, b) = match *self {
+            Instruction::Inp(a) => return write!(f, "inp {}", a),
+            Instruction::Add(a, b) => ("add", a, b),
+            Instruction::Mul(a, b) => ("mul", a, b),
+            Instruction::Div(a, b) => ("div", a, b),
+            Instruction::Mod(a, b) => ("mod", a, b),
+            Instruction::Eql(a, b) => ("eql", a, b),
+        };
+        write!(f, "{} {} {}", name, a, b)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// An `inp` ran after every input was read.
+    InputExhausted,
+    DivisionByZero,
+    /// A `mod` with a negative dividend or a non-positive divisor.
+    InvalidModulo,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Error::InputExhausted => write!(f, "no input left to read"),
+            Error::DivisionByZero => write!(f, "division by zero"),
+            Error::InvalidModulo => {
+                write!(f, "modulo of a negative number or by a non-positive one")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// The arithmetic logic unit, holding the four registers.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Alu([i64; 4]);
+
+impl Alu {
+    pub fn get(&self, register: Register) -> i64 {
+        self.0[register as usize]
+    }
+
+    fn value(&self, operand: Operand) -> i64 {
+        match operand {
+            Operand::Register(r) => self.get(r),
+            Operand::Number(n) => n,
+        }
+    }
+
+    pub fn execute(
+        &mut self,
+        instruction: Instruction,
+        inputs: &mut impl Iterator<Item = i64>,
+    ) -> Result<(), Error> {
+        let (a, result) = match instruction {
+            Instruction::Inp(a) => (a, inputs.next().ok_or(Error::InputExhausted)?),
+            Instruction::Add(a, b) => (a, self.get(a) + self.value(b)),
+            Instruction::Mul(a, b) => (a, self.get(a) * self.value(b)),
+            Instruction::Div(a, b) => match self.value(b) {
+                0 => return Err(Error::DivisionByZero),
+                b => (a, self.get(a) / b),
+            },
+            Instruction::Mod(a, b) => match (self.get(a), self.value(b)) {
+                (a, b) if a < 0 || b <= 0 => return Err(Error::InvalidModulo),
+                (x, b) => (a, x % b),
+            },
+            Instruction::Eql(a, b) => (a, (self.get(a) == self.value(b)) as i64),
+        };
+
+        self.0[a as usize] = result;
+        Ok(())
+    }
+
+    pub fn run(
+        &mut self,
+        program: &[Instruction],
+        inputs: impl IntoIterator<Item = i64>,
+    ) -> Result<(), Error> {
+        let mut inputs = inputs.into_iter();
+        for &instruction in program {
+            self.execute(instruction, &mut inputs)?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether MONAD accepts `number`, that is leaves 0 in `z`.
+pub fn is_valid(program: &[Instruction], number: u64) -> Result<bool, Error> {
+    let digits = number
+        .to_string()
+        .bytes()
+        .map(|b| (b - b'0') as i64)
+        .collect::<Vec<_>>();
+    if digits.contains(&0) {
+        return Ok(false);
+    }
+
+    let mut alu = Alu::default();
+    alu.run(program, digits)?;
+    Ok(alu.get(Register::Z) == 0)
+}
+
+/// Parameters of the routine MONAD repeats for each digit. It treats `z` as a
+/// stack of base 26 digits: a block either pushes `digit + offset`, or pops
+/// the top and pushes it back unless `top + check` is the digit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Block {
+    pub pop: bool,
+    pub check: i64,
+    pub offset: i64,
+}
+
+impl Block {
+    const LEN: usize = 18;
+
+    fn instructions(&self) -> [Instruction; Block::LEN] {
+        use {Instruction::*, Operand::Number, Register::*};
+        let reg = Operand::Register;
+
+        [
+            Inp(W),
+            Mul(X, Number(0)),
+            Add(X, reg(Z)),
+            Mod(X, Number(26)),
+            Div(Z, Number(if self.pop { 26 } else { 1 })),
+            Add(X, Number(self.check)),
+            Eql(X, reg(W)),
+            Eql(X, Number(0)),
+            Mul(Y, Number(0)),
+            Add(Y, Number(25)),
+            Mul(Y, reg(X)),
+            Add(Y, Number(1)),
+            Mul(Z, reg(Y)),
+            Mul(Y, Number(0)),
+            Add(Y, reg(W)),
+            Add(Y, Number(self.offset)),
+            Mul(Y, reg(X)),
+            Add(Z, reg(Y)),
+        ]
+    }
+
+    fn from_instructions(instructions: &[Instruction]) -> Option<Block> {
+        use {Instruction::*, Operand::Number, Register::*};
+
+        let block = match instructions {
+            [_, _, _, _, Div(Z, Number(div)), Add(X, Number(check)), .., Add(Y, Number(offset)), _, _] => {
+                Block {
+                    pop: match div {
+                        1 => false,
+                        26 => true,
+                        _ => return None,
+                    },
+                    check: *check,
+                    offset: *offset,
+                }
+            }
+            _ => return None,
+        };
+
+        (instructions == block.instructions()).then_some(block)
+    }
+}
+
+impl Display for Block {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let kind = if self.pop { "pop" } else { "push" };
+        write!(f, "{} (check {}, offset {})", kind, self.check, self.offset)
+    }
+}
+
+/// The digit blocks of a program, if it is made only of them.
+pub fn blocks(program: &[Instruction]) -> Option<Vec<Block>> {
+    if program.len() != DIGITS * Block::LEN {
+        return None;
+    }
+    program
+        .chunks_exact(Block::LEN)
+        .map(Block::from_instructions)
+        .collect()
+}
+
+/// Requirement that digit `pop` equals digit `push` plus `diff`, both from 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Constraint {
+    pub push: usize,
+    pub pop: usize,
+    pub diff: i64,
+}
+
+impl Display for Constraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let sign = if self.diff < 0 { '-' } else { '+' };
+        write!(
+            f,
+            "digit {} = digit {} {} {}",
+            self.pop + 1,
+            self.push + 1,
+            sign,
+            self.diff.abs()
+        )
+    }
+}
+
+/// Pairs each popping block with the block that pushed what it pops.
+///
+/// Valid numbers can't let any pushing block compare equal, or `z` would
+/// never get back to 0, so they are exactly those where each popping block
+/// sees its digit. Returns `None` if some pushing block could compare equal
+/// or the pushes and pops don't balance.
+pub fn constraints(blocks: &[Block]) -> Option<Vec<Constraint>> {
+    let mut stack = Vec::new();
+    let mut constraints = Vec::new();
+
+    for (i, block) in blocks.iter().enumerate() {
+        if !block.pop {
+            if block.check <= 9 {
+                return None;
+            }
+            stack.push(i);
+        } else {
+            let push = stack.pop()?;
+            let diff = blocks[push].offset + block.check;
+            constraints.push(Constraint { push, pop: i, diff });
+        }
+    }
+
+    stack.is_empty().then_some(constraints)
+}
+
+/// The model number that takes digits the earliest in `order` satisfying
+/// every constraint, if there is one.
+fn search_constraints(constraints: &[Constraint], order: [i64; 9]) -> Option<u64> {
+    let mut digits = [0; DIGITS];
+
+    for c in constraints {
+        let push = order
+            .into_iter()
+            .find(|&d| (1..=9).contains(&(d + c.diff)))?;
+        digits[c.push] = push;
+        digits[c.pop] = push + c.diff;
+    }
+
+    Some(digits.iter().fold(0, |n, &d| n * 10 + d as u64))
+}
+
+/// Depth-first search over the register states after each `inp`, trying
+/// digits in `order` and remembering the states no digits lead to a valid
+/// number from.
+///
+/// Works on any program reading [`DIGITS`] inputs, but for pruning assumes
+/// that, as in MONAD, `z` only gets smaller through `div z` instructions: a
+/// state is given up on when those left can't bring its `z` down to 0.
+pub fn search_states(program: &[Instruction], order: [i64; 9]) -> Result<Option<u64>, Error> {
+    let starts = program
+        .iter()
+        .enumerate()
+        .filter(|(_, i)| matches!(i, Instruction::Inp(_)))
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    if starts.len() != DIGITS || starts[0] != 0 {
+        return Ok(None);
+    }
+
+    let segments = (0..DIGITS)
+        .map(|i| &program[starts[i]..*starts.get(i + 1).unwrap_or(&program.len())])
+        .collect::<Vec<_>>();
+
+    // How many times `z` can still be divided, saturating for large bounds.
+    let mut bounds = vec![1i64; DIGITS + 1];
+    for i in (0..DIGITS).rev() {
+        bounds[i] = segments[i]
+            .iter()
+            .filter_map(|instruction| match instruction {
+                Instruction::Div(Register::Z, Operand::Number(n)) => Some(n.abs()),
+                _ => None,
+            })
+            .fold(bounds[i + 1], i64::saturating_mul);
+    }
+
+    struct Search<'a> {
+        segments: Vec<&'a [Instruction]>,
+        bounds: Vec<i64>,
+        order: [i64; 9],
+        dead: HashSet<(usize, Alu)>,
+    }
+
+    impl Search<'_> {
+        fn from(&mut self, i: usize, alu: Alu) -> Result<Option<u64>, Error> {
+            if i == DIGITS {
+                return Ok((alu.get(Register::Z) == 0).then_some(0));
+            }
+            if alu.get(Register::Z).abs() >= self.bounds[i] || self.dead.contains(&(i, alu)) {
+                return Ok(None);
+            }
+
+            for digit in self.order {
+                let mut next = alu;
+                next.run(self.segments[i], [digit])?;
+
+                if let Some(rest) = self.from(i + 1, next)? {
+                    let scale = 10u64.pow((DIGITS - i - 1) as u32);
+                    return Ok(Some(digit as u64 * scale + rest));
+                }
+            }
+
+            self.dead.insert((i, alu));
+            Ok(None)
+        }
+    }
+
+    let mut search = Search {
+        segments,
+        bounds,
+        order,
+        dead: HashSet::new(),
+    };
+    search.from(0, Alu::default())
+}
+
+/// The model number MONAD accepts that takes digits the earliest in
+/// `order`, through the block analysis when the program allows it.
+fn model_number(program: &[Instruction], order: [i64; 9]) -> u64 {
+    let analysed = blocks(program)
+        .and_then(|blocks| constraints(&blocks))
+        .map(|constraints| search_constraints(&constraints, order));
+
+    match analysed {
+        Some(number) => number,
+        None => search_states(program, order).unwrap_or_else(|e| panic!("{}", e)),
+    }
+    .expect("MONAD accepts no model number")
+}
+
+fn solve_part_one(input: &ParsedInput) -> u64 {
+    model_number(input, LARGEST)
+}
+
+fn solve_part_two(input: &ParsedInput) -> u64 {
+    model_number(input, SMALLEST)
+}
+
+fn register(input: Input<'_>) -> PResult<'_, Register> {
+    satisfy(|c| matches!(c, 'w'..='z'), "register")
+        .map(|c| match c {
+            'w' => Register::W,
+            'x' => Register::X,
+            'y' => Register::Y,
+            _ => Register::Z,
+        })
+        .parse(input)
+}
+
+fn operand(input: Input<'_>) -> PResult<'_, Operand> {
+    match register.parse(input) {
+        Ok((r, rest)) => Ok((Operand::Register(r), rest)),
+        Err(_) => integer().map(Operand::Number).parse(input),
+    }
+}
+
+fn instruction(input: Input<'_>) -> PResult<'_, Instruction> {
+    let (name, rest) = word().skip(tag(" ")).parse(input)?;
+    let (a, rest) = register.parse(rest)?;
+
+    let make = match name {
+        "inp" => return Ok((Instruction::Inp(a), rest)),
+        "add" => Instruction::Add,
+        "mul" => Instruction::Mul,
+        "div" => Instruction::Div,
+        "mod" => Instruction::Mod,
+        "eql" => Instruction::Eql,
+        _ => return input.fail("instruction"),
+    };
+    let (b, rest) = operand.parse(tag(" ").parse(rest)?.1)?;
+    Ok((make(a, b), rest))
+}
+
+fn parse_input(input: &str) -> ParsedInput {
+    parse::parse(lines(instruction), input).unwrap_or_else(|e| panic!("{}", e))
+}
+
+pub fn solve() {
+    solve_input(INPUT);
+}
+
+pub fn solve_input(input: &str) {
+    let input = parse_input(input);
+
+    let result = solve_part_one(&input);
+    println!("Part #1: {}", result);
+
+    let result = solve_part_two(&input);
+    println!("Part #2: {}", result);
+}
+
+common::export_plugin!(2021, 24, solve_input);
+
+pub fn explain() -> Explanation {
+    explain_input(INPUT)
+}
+
+/// The parameters of each digit block and the constraints they put on the
+/// digits, for programs made of them.
+pub fn explain_input(input: &str) -> Explanation {
+    let program = parse_input(input);
+
+    let mut explanation = Explanation::new();
+    let Some(blocks) = blocks(&program) else {
+        explanation.field("error", "The program isn't made of digit blocks");
+        return explanation;
+    };
+
+    let disassembly = blocks
+        .iter()
+        .enumerate()
+        .map(|(i, block)| format!("digit {}: {}", i + 1, block))
+        .collect::<Vec<_>>();
+    explanation.field("blocks", disassembly);
+
+    match constraints(&blocks) {
+        Some(constraints) => explanation.field(
+            "constraints",
+            constraints
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>(),
+        ),
+        None => explanation.field("error", "The blocks don't pair up"),
+    };
+    explanation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The answers for a fetched input aren't committed, so only the sample's
+    // are checked.
+    #[cfg(any(debug_assertions, not(puzzle_input)))]
+    #[test]
+    fn test_part_one() {
+        let result = solve_part_one(&parse_input(INPUT));
+
+        assert_eq!(result, 99394899891971);
+    }
+
+    #[bench]
+    fn bench_part_one(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_one(&input));
+    }
+
+    #[cfg(any(debug_assertions, not(puzzle_input)))]
+    #[test]
+    fn test_part_two() {
+        let result = solve_part_two(&parse_input(INPUT));
+
+        assert_eq!(result, 92171126131911);
+    }
+
+    #[bench]
+    fn bench_part_two(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_two(&input));
+    }
+
+    #[test]
+    fn interpreter() {
+        let run = |src: &str, inputs: &[i64]| {
+            let mut alu = Alu::default();
+            alu.run(&parse_input(src), inputs.iter().copied())
+                .map(|_| alu)
+        };
+
+        let negate = "inp x\nmul x -1\n";
+        assert_eq!(run(negate, &[7]).unwrap().get(Register::X), -7);
+
+        let three_times = "inp z\ninp x\nmul z 3\neql z x\n";
+        assert_eq!(run(three_times, &[2, 6]).unwrap().get(Register::Z), 1);
+        assert_eq!(run(three_times, &[2, 7]).unwrap().get(Register::Z), 0);
+
+        let binary = "inp w\nadd z w\nmod z 2\ndiv w 2\nadd y w\nmod y 2\n\
+                      div w 2\nadd x w\nmod x 2\ndiv w 2\nmod w 2\n";
+        let alu = run(binary, &[11]).unwrap();
+        assert_eq!(alu, Alu([1, 0, 1, 1]));
+
+        assert_eq!(run(negate, &[]), Err(Error::InputExhausted));
+        assert_eq!(run("div x 0", &[]), Err(Error::DivisionByZero));
+        assert_eq!(run("add x -3\nmod x 2", &[]), Err(Error::InvalidModulo));
+    }
+
+    #[test]
+    fn disassembly() {
+        let program = parse_input(include_str!("../sample.TXT"));
+        let src = program
+            .iter()
+            .map(|i| format!("{}\n", i))
+            .collect::<String>();
+        assert_eq!(src, include_str!("../sample.TXT"));
+
+        let blocks = blocks(&program).unwrap();
+        assert_eq!(
+            blocks[3],
+            Block {
+                pop: true,
+                check: -8,
+                offset: 10
+            }
+        );
+        assert_eq!(
+            blocks
+                .iter()
+                .flat_map(Block::instructions)
+                .collect::<Vec<_>>(),
+            program
+        );
+
+        let constraints = constraints(&blocks).unwrap();
+        assert_eq!(constraints.len(), DIGITS / 2);
+        assert_eq!(constraints[0].to_string(), "digit 4 = digit 3 + 6");
+        assert_eq!(constraints[6].to_string(), "digit 14 = digit 1 - 8");
+
+        let text = explain_input(include_str!("../sample.TXT")).text();
+        assert!(text.contains("digit 1: push (check 11, offset 6)"));
+        assert!(text.contains("digit 4: pop (check -8, offset 10)"));
+    }
+
+    #[test]
+    fn searches_agree() {
+        let program = parse_input(include_str!("../sample.TXT"));
+
+        for order in [LARGEST, SMALLEST] {
+            let number = model_number(&program, order);
+            assert_eq!(search_states(&program, order), Ok(Some(number)));
+            assert_eq!(is_valid(&program, number), Ok(true));
+            assert_eq!(is_valid(&program, number - 1), Ok(false));
+        }
+        assert_eq!(is_valid(&program, 99999999999999), Ok(false));
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain_input(include_str!("../sample.TXT"));
+
+        assert_eq!(
+            explanation.text(),
+            "blocks:\n  - digit 1: push (check 11, offset 6)\n  \
+             - digit 2: push (check 13, offset 14)\n  \
+             - digit 3: push (check 15, offset 14)\n  \
+             - digit 4: pop (check -8, offset 10)\n  \
+             - digit 5: push (check 13, offset 9)\n  \
+             - digit 6: push (check 15, offset 12)\n  \
+             - digit 7: pop (check -11, offset 8)\n  \
+             - digit 8: pop (check -4, offset 13)\n  \
+             - digit 9: pop (check -15, offset 12)\n  \
+             - digit 10: push (check 14, offset 6)\n  \
+             - digit 11: push (check 14, offset 9)\n  \
+             - digit 12: pop (check -1, offset 15)\n  \
+             - digit 13: pop (check -8, offset 4)\n  \
+             - digit 14: pop (check -14, offset 10)\n\
+             constraints:\n  - digit 4 = digit 3 + 6\n  \
+             - digit 7 = digit 6 + 1\n  - digit 8 = digit 5 + 5\n  \
+             - digit 9 = digit 2 - 1\n  - digit 12 = digit 11 + 8\n  \
+             - digit 13 = digit 10 - 2\n  - digit 14 = digit 1 - 8\n"
+        );
+
+        let explanation = explain_input("inp x\nmul x -1\n");
+        assert_eq!(
+            explanation.text(),
+            "error: The program isn't made of digit blocks\n"
+        );
+
+        // Every block pushing leaves nothing for the constraints to pair.
+        let pushes = include_str!("../sample.TXT").replace("div z 26", "div z 1");
+        let text = explain_input(&pushes).text();
+        assert!(text.contains("  - digit 4: push (check -8, offset 10)\n"));
+        assert!(text.ends_with("\nerror: The blocks don't pair up\n"));
+    }
+}
@@ -0,0 +1,15 @@
+//! Sets `cfg(puzzle_input)` once the puzzle input has been fetched, so that
+//! release builds run on it rather than on the sample.
+
+use std::path::Path;
+
+fn main() {
+    println!("cargo::rustc-check-cfg=cfg(puzzle_input)");
+
+    // Without a `rerun-if-changed`, cargo reruns this whenever a file of the
+    // package changes, which includes the input turning up.
+    if Path::new("input.TXT").is_file() {
+        println!("cargo::rerun-if-changed=input.TXT");
+        println!("cargo::rustc-cfg=puzzle_input");
+    }
+}
@@ -0,0 +1,413 @@
+#![feature(test)]
+extern crate test;
+
+use std::fmt::Display;
+
+use common::{
+    explain::Explanation,
+    grid::Grid,
+    svg::{self, Svg},
+};
+
+#[cfg(any(debug_assertions, not(puzzle_input)))]
+const INPUT: &str = include_str!("../sample.TXT");
+
+#[cfg(all(not(debug_assertions), puzzle_input))]
+const INPUT: &str = include_str!("../input.TXT");
+
+type ParsedInput = Seafloor;
+
+const WORD: usize = u64::BITS as usize;
+
+/// Both herds on a grid that wraps around at its edges, as one bit per cell
+/// and herd. Every row starts on a new word, with the bits past the width
+/// left clear.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Seafloor {
+    width: usize,
+    height: usize,
+    /// Words per row.
+    words: usize,
+    east: Vec<u64>,
+    south: Vec<u64>,
+}
+
+impl Seafloor {
+    fn new(width: usize, height: usize) -> Self {
+        let words = width.div_ceil(WORD);
+        Seafloor {
+            width,
+            height,
+            words,
+            east: vec![0; words * height],
+            south: vec![0; words * height],
+        }
+    }
+
+    fn row(&self, y: usize) -> std::ops::Range<usize> {
+        y * self.words..(y + 1) * self.words
+    }
+
+    fn get(herd: &[u64], x: usize) -> bool {
+        herd[x / WORD] >> (x % WORD) & 1 == 1
+    }
+
+    fn set(herd: &mut [u64], x: usize) {
+        herd[x / WORD] |= 1 << (x % WORD);
+    }
+
+    /// The row moved one cell left with wraparound, each cell getting the
+    /// bit of the one to its right.
+    fn shift_left(&self, row: &[u64]) -> Vec<u64> {
+        let mut out = (0..self.words)
+            .map(|i| row[i] >> 1 | row.get(i + 1).map_or(0, |next| next << (WORD - 1)))
+            .collect::<Vec<_>>();
+        if row[0] & 1 == 1 {
+            Self::set(&mut out, self.width - 1);
+        }
+        out
+    }
+
+    /// The row moved one cell right with wraparound, each cell getting the
+    /// bit of the one to its left.
+    fn shift_right(&self, row: &[u64]) -> Vec<u64> {
+        let mut out = (0..self.words)
+            .map(|i| row[i] << 1 | if i > 0 { row[i - 1] >> (WORD - 1) } else { 0 })
+            .collect::<Vec<_>>();
+        if !self.width.is_multiple_of(WORD) {
+            out[self.words - 1] &= (1 << (self.width % WORD)) - 1;
+        }
+        if Self::get(row, self.width - 1) {
+            out[0] |= 1;
+        }
+        out
+    }
+
+    /// Moves every east-facing cucumber with a free cell to its right,
+    /// returning how many moved.
+    fn step_east(&mut self) -> u32 {
+        let mut moved = 0;
+
+        for y in 0..self.height {
+            let range = self.row(y);
+            let (east, south) = (&self.east[range.clone()], &self.south[range.clone()]);
+
+            let occupied = east
+                .iter()
+                .zip(south)
+                .map(|(e, s)| e | s)
+                .collect::<Vec<_>>();
+            let blocked = self.shift_left(&occupied);
+            let movers = east
+                .iter()
+                .zip(&blocked)
+                .map(|(e, b)| e & !b)
+                .collect::<Vec<_>>();
+            let arrived = self.shift_right(&movers);
+
+            for (i, word) in self.east[range].iter_mut().enumerate() {
+                *word = *word & !movers[i] | arrived[i];
+                moved += movers[i].count_ones();
+            }
+        }
+
+        moved
+    }
+
+    /// Moves every south-facing cucumber with a free cell below it, all at
+    /// once, returning how many moved.
+    fn step_south(&mut self) -> u32 {
+        let movers = (0..self.height)
+            .flat_map(|y| {
+                let below = self.row((y + 1) % self.height);
+                self.row(y)
+                    .zip(below)
+                    .map(|(i, j)| self.south[i] & !(self.east[j] | self.south[j]))
+            })
+            .collect::<Vec<_>>();
+
+        for y in 0..self.height {
+            let above = self.row((y + self.height - 1) % self.height);
+            for (i, j) in self.row(y).zip(above) {
+                self.south[i] = self.south[i] & !movers[i] | movers[j];
+            }
+        }
+
+        movers.iter().map(|m| m.count_ones()).sum()
+    }
+
+    /// Moves the east-facing herd then the south-facing one, returning
+    /// whether any cucumber moved.
+    pub fn step(&mut self) -> bool {
+        let east = self.step_east();
+        let south = self.step_south();
+        east + south > 0
+    }
+
+    fn count(herd: &[u64]) -> u32 {
+        herd.iter().map(|word| word.count_ones()).sum()
+    }
+
+    fn svg(&self) -> Svg {
+        let cell = svg::cell_size(self.width, self.height);
+        let (width, height) = (self.width as f64 * cell, self.height as f64 * cell);
+
+        let mut svg = Svg::new(width, height);
+        svg.rect(0.0, 0.0, width, height, "#cde");
+        for y in 0..self.height {
+            let (east, south) = (&self.east[self.row(y)], &self.south[self.row(y)]);
+            for x in 0..self.width {
+                let colour = match (Self::get(east, x), Self::get(south, x)) {
+                    (true, _) => "#2a7",
+                    (_, true) => "#a52",
+                    _ => continue,
+                };
+                svg.rect(x as f64 * cell, y as f64 * cell, cell, cell, colour);
+            }
+        }
+
+        svg
+    }
+}
+
+impl Display for Seafloor {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for y in 0..self.height {
+            let (east, south) = (&self.east[self.row(y)], &self.south[self.row(y)]);
+            let row = (0..self.width)
+                .map(|x| match (Self::get(east, x), Self::get(south, x)) {
+                    (true, _) => '>',
+                    (_, true) => 'v',
+                    _ => '.',
+                })
+                .collect::<String>();
+            writeln!(f, "{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+/// Number of the first step on which no sea cucumber moves.
+fn steps_until_still(seafloor: &Seafloor) -> usize {
+    let mut seafloor = seafloor.clone();
+    let mut steps = 1;
+    while seafloor.step() {
+        steps += 1;
+    }
+    steps
+}
+
+fn solve_part_one(input: &ParsedInput) -> usize {
+    steps_until_still(input)
+}
+
+fn parse_input(input: &str) -> ParsedInput {
+    let grid = Grid::parse(input).unwrap_or_else(|e| panic!("{}", e));
+    let mut seafloor = Seafloor::new(grid.width(), grid.height());
+
+    for (y, row) in grid.rows().enumerate() {
+        let range = seafloor.row(y);
+        for (x, &cell) in row.iter().enumerate() {
+            match cell {
+                b'>' => Seafloor::set(&mut seafloor.east[range.clone()], x),
+                b'v' => Seafloor::set(&mut seafloor.south[range.clone()], x),
+                b'.' => {}
+                _ => panic!("Unexpected {:?} at row {}", cell as char, y + 1),
+            }
+        }
+    }
+
+    seafloor
+}
+
+pub fn solve() {
+    solve_input(INPUT);
+}
+
+pub fn solve_input(input: &str) {
+    let input = parse_input(input);
+
+    let result = solve_part_one(&input);
+    println!("Part #1: {}", result);
+}
+
+common::export_plugin!(2021, 25, solve_input);
+
+pub fn explain() -> Explanation {
+    explain_input(INPUT)
+}
+
+/// The size of the seafloor and of both herds, and how many steps it takes
+/// them to stop moving.
+pub fn explain_input(input: &str) -> Explanation {
+    let seafloor = parse_input(input);
+
+    let mut part_one = Explanation::new();
+    part_one.field("steps", steps_until_still(&seafloor));
+
+    let mut explanation = Explanation::new();
+    explanation
+        .field(
+            "seafloor",
+            format!("{}x{}", seafloor.width, seafloor.height),
+        )
+        .field("east_facing", Seafloor::count(&seafloor.east))
+        .field("south_facing", Seafloor::count(&seafloor.south))
+        .field("part_one", part_one);
+    explanation
+}
+
+pub fn svg() -> String {
+    svg_input(INPUT)
+}
+
+/// The herds once they stopped moving.
+pub fn svg_input(input: &str) -> String {
+    let mut seafloor = parse_input(input);
+    while seafloor.step() {}
+    seafloor.svg().to_string()
+}
+
+pub fn frames() -> impl Iterator<Item = Svg> {
+    frames_input(INPUT)
+}
+
+/// The herds before any step and after each step until they stop moving.
+///
+/// Drawn lazily, as there can be hundreds of steps on a large seafloor.
+pub fn frames_input(input: &str) -> impl Iterator<Item = Svg> {
+    let mut seafloor = Some(parse_input(input));
+
+    std::iter::from_fn(move || {
+        let current = seafloor.take()?;
+        let frame = current.svg();
+
+        let mut next = current;
+        if next.step() {
+            seafloor = Some(next);
+        }
+        Some(frame)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Plain byte grid version of a step, to check the bit twiddling against.
+    fn naive_step(rows: &mut [Vec<u8>]) {
+        let (width, height) = (rows[0].len(), rows.len());
+
+        for (herd, (dx, dy)) in [(b'>', (1, 0)), (b'v', (0, 1))] {
+            let before = rows.to_vec();
+            for y in 0..height {
+                for x in 0..width {
+                    let (nx, ny) = ((x + dx) % width, (y + dy) % height);
+                    if before[y][x] == herd && before[ny][nx] == b'.' {
+                        rows[y][x] = b'.';
+                        rows[ny][nx] = herd;
+                    }
+                }
+            }
+        }
+    }
+
+    // The answers for a fetched input aren't committed, so only the sample's
+    // are checked.
+    #[cfg(any(debug_assertions, not(puzzle_input)))]
+    #[test]
+    fn test_part_one() {
+        let result = solve_part_one(&parse_input(INPUT));
+
+        assert_eq!(result, 58);
+    }
+
+    #[bench]
+    fn bench_part_one(b: &mut test::Bencher) {
+        let input = parse_input(INPUT);
+        b.iter(|| solve_part_one(&input));
+    }
+
+    #[test]
+    fn steps() {
+        let mut row = parse_input("...>>>>>...\n");
+        row.step();
+        assert_eq!(row.to_string(), "...>>>>.>..\n");
+        row.step();
+        assert_eq!(row.to_string(), "...>>>.>.>.\n");
+
+        // The east-facing herd moves first, then the south-facing one
+        // wraps around the bottom edge.
+        let mut seafloor = parse_input("..........\n.>v....v..\n.......>..\n..........\n");
+        seafloor.step();
+        assert_eq!(
+            seafloor.to_string(),
+            "..........\n.>........\n..v....v>.\n..........\n"
+        );
+
+        let mut wrapping =
+            parse_input("...>...\n.......\n......>\nv.....>\n......>\n.......\n..vvv..\n");
+        for _ in 0..4 {
+            wrapping.step();
+        }
+        assert_eq!(
+            wrapping.to_string(),
+            ">......\n..v....\n..>.v..\n.>.v...\n...>...\n.......\nv......\n"
+        );
+    }
+
+    #[test]
+    fn wide_seafloor() {
+        // Rows spanning several words, the last partly, filled pseudo-randomly.
+        let (width, height) = (150, 40);
+        let mut seed = 25u64;
+        let mut rows = (0..height)
+            .map(|_| {
+                (0..width)
+                    .map(|_| {
+                        seed = seed
+                            .wrapping_mul(6364136223846793005)
+                            .wrapping_add(1442695040888963407);
+                        b".>v"[(seed >> 33) as usize % 3]
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut seafloor = parse_input(&String::from_utf8(rows.join(&b'\n')).unwrap());
+        for _ in 0..30 {
+            naive_step(&mut rows);
+            seafloor.step();
+
+            let expected = rows
+                .iter()
+                .map(|row| String::from_utf8_lossy(row) + "\n")
+                .collect::<String>();
+            assert_eq!(seafloor.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn drawings() {
+        let frames = frames_input(include_str!("../sample.TXT")).collect::<Vec<_>>();
+
+        assert_eq!(frames.len(), 58);
+        // The background, then the 49 cucumbers of the sample.
+        assert_eq!(frames[0].to_string().matches("<rect ").count(), 50);
+        assert_eq!(
+            svg_input(include_str!("../sample.TXT")),
+            frames[57].to_string()
+        );
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain_input(include_str!("../sample.TXT"));
+
+        assert_eq!(
+            explanation.text(),
+            "seafloor: 10x9\neast_facing: 23\nsouth_facing: 26\n\
+             part_one:\n  steps: 58\n"
+        );
+    }
+}
@@ -0,0 +1,43 @@
+use std::path::Path;
+
+use common::{cli::Args, explain::Format, svg};
+use solution::{explain, explain_input, frames, frames_input, solve, solve_input, svg, svg_input};
+
+fn main() {
+    let args = Args::from_env();
+    let input = args.read_input();
+
+    if let Some(dir) = &args.frames {
+        let dir_path = Path::new(dir);
+        let count = match &input {
+            Some(input) => svg::write_frames(dir_path, frames_input(input)),
+            None => svg::write_frames(dir_path, frames()),
+        }
+        .expect("Failed to write frames");
+        println!("Wrote {} frames to {}", count, dir);
+        return;
+    }
+
+    if args.svg {
+        match &input {
+            Some(input) => print!("{}", svg_input(input)),
+            None => print!("{}", svg()),
+        }
+        return;
+    }
+
+    if args.explain != Some(Format::Json) {
+        match &input {
+            Some(input) => solve_input(input),
+            None => solve(),
+        }
+    }
+
+    if let Some(format) = args.explain {
+        let explanation = match &input {
+            Some(input) => explain_input(input),
+            None => explain(),
+        };
+        print!("{}", format.render(&explanation));
+    }
+}
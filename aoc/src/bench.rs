@@ -0,0 +1,362 @@
+//! Benchmark history, so slowdowns between commits get noticed.
+//!
+//! Every `aoc bench run` saves the `#[bench]` results of the days it ran to
+//! `target/aoc/bench/<machine>/<commit>.json`, replacing older results of
+//! the same days. Timings are only ever compared on the same machine.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{runner, scaffold::Puzzle, table::Table, Context, Result};
+
+/// Directory holding the history, relative to the repo root.
+const DIR: &str = "target/aoc/bench";
+
+/// Results of a single `#[bench]` function over repeated runs of
+/// `cargo bench`: the ns/iter libtest reported in each run.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Timing {
+    pub samples: Vec<f64>,
+}
+
+/// One-sided critical values of Student's t at the 5% level, for 1 to 30
+/// degrees of freedom.
+const T_CRITICAL: [f64; 30] = [
+    6.314, 2.920, 2.353, 2.132, 2.015, 1.943, 1.895, 1.860, 1.833, 1.812, 1.796, 1.782, 1.771,
+    1.761, 1.753, 1.746, 1.740, 1.734, 1.729, 1.725, 1.721, 1.717, 1.714, 1.711, 1.708, 1.706,
+    1.703, 1.701, 1.699, 1.697,
+];
+
+/// The critical value for more than 30 degrees of freedom.
+const T_CRITICAL_LIMIT: f64 = 1.645;
+
+/// Critical value of t for `df` degrees of freedom, rounded down to the
+/// value above it in the table so that the test stays conservative.
+fn t_critical(df: f64) -> f64 {
+    match df.floor() as usize {
+        0 => T_CRITICAL[0],
+        df if df <= T_CRITICAL.len() => T_CRITICAL[df - 1],
+        _ => T_CRITICAL_LIMIT,
+    }
+}
+
+impl Timing {
+    pub fn mean(&self) -> f64 {
+        self.samples.iter().sum::<f64>() / self.samples.len() as f64
+    }
+
+    /// Unbiased sample variance.
+    pub fn variance(&self) -> f64 {
+        let mean = self.mean();
+        let squares = self.samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>();
+        squares / (self.samples.len() as f64 - 1.0)
+    }
+
+    /// Whether `self` is slower than `base` by more than `threshold` (a
+    /// fraction of the baseline mean), with the slowdown significant at the
+    /// 5% level under a one-sided Welch's t-test. Needs at least two samples
+    /// on each side.
+    pub fn regressed_from(&self, base: &Timing, threshold: f64) -> bool {
+        let (n, m) = (self.samples.len() as f64, base.samples.len() as f64);
+        if n < 2.0 || m < 2.0 {
+            return false;
+        }
+
+        let slowdown = self.mean() - base.mean();
+        if slowdown <= base.mean() * threshold {
+            return false;
+        }
+
+        let (a, b) = (self.variance() / n, base.variance() / m);
+        if a + b == 0.0 {
+            return true;
+        }
+
+        let t = slowdown / (a + b).sqrt();
+        // Welch–Satterthwaite approximation of the degrees of freedom.
+        let df = (a + b).powi(2) / (a.powi(2) / (n - 1.0) + b.powi(2) / (m - 1.0));
+        t > t_critical(df)
+    }
+
+    pub fn improved_from(&self, base: &Timing, threshold: f64) -> bool {
+        base.regressed_from(self, threshold)
+    }
+}
+
+/// Timings of one commit on one machine, by day and then benchmark name.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub days: BTreeMap<String, BTreeMap<String, Timing>>,
+}
+
+/// Parses the `test tests::bench_part_one ... bench: 5,514.31 ns/iter
+/// (+/- 684.54)` lines of `cargo bench` into their ns/iter, keyed by the
+/// function name without its module and `bench_` prefix.
+pub fn parse_output(output: &str) -> BTreeMap<String, f64> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (name, result) = line.strip_prefix("test ")?.split_once(" ... bench:")?;
+            let (ns, _) = result.split_once("ns/iter")?;
+
+            let name = name.rsplit("::").next()?;
+            let name = name.strip_prefix("bench_").unwrap_or(name);
+            let ns = ns.trim().replace(',', "").parse().ok()?;
+            Some((name.to_owned(), ns))
+        })
+        .collect()
+}
+
+/// History of the current machine.
+pub struct History {
+    dir: PathBuf,
+}
+
+impl History {
+    pub fn new(root: &Path) -> Self {
+        History {
+            dir: root.join(DIR).join(machine()),
+        }
+    }
+
+    fn path(&self, commit: &str) -> PathBuf {
+        self.dir.join(commit).with_extension("json")
+    }
+
+    pub fn load(&self, commit: &str) -> Result<Option<Record>> {
+        let path = self.path(commit);
+        match fs::read_to_string(&path) {
+            Ok(json) => Ok(Some(
+                serde_json::from_str(&json).map_err(|e| format!("{}: {}", path.display(), e))?,
+            )),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, commit: &str, record: &Record) -> Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.path(commit), serde_json::to_string_pretty(record)?)?;
+        Ok(())
+    }
+}
+
+/// Name of the machine the benchmarks run on.
+fn machine() -> String {
+    let name = Command::new("uname")
+        .arg("-n")
+        .output()
+        .ok()
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .map(|name| name.trim().to_owned())
+        .unwrap_or_default();
+
+    if name.is_empty() {
+        String::from("unknown")
+    } else {
+        name
+    }
+}
+
+/// Abbreviated hash of `rev`, with a `-dirty` suffix for `HEAD` when tracked
+/// files have uncommitted changes.
+pub fn commit(root: &Path, rev: &str) -> Result<String> {
+    let git = |args: &[&str]| -> Result<String> {
+        let out = Command::new("git").args(args).current_dir(root).output()?;
+        if !out.status.success() {
+            let err = String::from_utf8_lossy(&out.stderr);
+            return Err(err.lines().next().unwrap_or("git failed").into());
+        }
+        Ok(String::from_utf8(out.stdout)?.trim().to_owned())
+    };
+
+    let hash = git(&["rev-parse", "--short=12", "--verify", rev])?;
+    if rev == "HEAD" && !git(&["status", "--porcelain", "--untracked-files=no"])?.is_empty() {
+        return Ok(format!("{}-dirty", hash));
+    }
+    Ok(hash)
+}
+
+/// Benchmarks `days` `runs` times each and adds their timings to the
+/// history of the current commit.
+pub fn run(ctx: &Context, days: &[Puzzle], runs: u32) -> Result<()> {
+    let history = History::new(&ctx.root);
+    let commit = commit(&ctx.root, "HEAD")?;
+    let mut record = history.load(&commit)?.unwrap_or_default();
+
+    'days: for &day in days {
+        let mut timings: BTreeMap<String, Timing> = BTreeMap::new();
+
+        for _ in 0..runs {
+            let mut cmd = Command::new("cargo");
+            cmd.args(["bench", "--offline"])
+                .current_dir(day.dir(&ctx.root))
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped());
+
+            let (success, stdout, _) =
+                runner::wait_with_timeout(cmd.spawn()?, runner::timeout(ctx))
+                    .map_err(|e| format!("{}: {}", day, e))?;
+            let results = parse_output(&stdout);
+
+            if !success || results.is_empty() {
+                println!("{}: no benchmark results", day);
+                continue 'days;
+            }
+            for (name, ns) in results {
+                timings.entry(name).or_default().samples.push(ns);
+            }
+        }
+
+        for (name, timing) in &timings {
+            println!(
+                "{} {}: {:.0} ns/iter (sd {:.0}, {} runs)",
+                day,
+                name,
+                timing.mean(),
+                timing.variance().sqrt(),
+                timing.samples.len()
+            );
+        }
+        record.days.insert(day.to_string(), timings);
+        history.save(&commit, &record)?;
+    }
+
+    println!("Saved as {}", commit);
+    Ok(())
+}
+
+/// Benchmarks slower or faster in `current` than in `base`.
+pub fn compare(base: &Record, current: &Record, threshold: f64) -> (Table, bool) {
+    let mut table = Table::new(["Day", "Bench", "Baseline", "Current", "Change", ""]);
+    let mut regressed = false;
+
+    for (day, timings) in &current.days {
+        for (name, timing) in timings {
+            let Some(base) = base.days.get(day).and_then(|t| t.get(name)) else {
+                continue;
+            };
+
+            let verdict = if timing.regressed_from(base, threshold) {
+                regressed = true;
+                "slower"
+            } else if timing.improved_from(base, threshold) {
+                "faster"
+            } else {
+                ""
+            };
+
+            table.row([
+                day.clone(),
+                name.clone(),
+                format!("{:.0} ns", base.mean()),
+                format!("{:.0} ns", timing.mean()),
+                format!("{:+.1}%", (timing.mean() / base.mean() - 1.0) * 100.0),
+                verdict.to_owned(),
+            ]);
+        }
+    }
+
+    (table, regressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = "
+running 4 tests
+test tests::test_part_one ... ignored
+test tests::bench_part_one ... bench:       5,514.31 ns/iter (+/- 684.54)
+test tests::bench_part_two ... bench:      10,196 ns/iter (+/- 4,779)
+
+test result: ok. 0 passed; 0 failed; 2 ignored; 2 measured; 0 filtered out
+";
+
+    fn timing(samples: &[f64]) -> Timing {
+        Timing {
+            samples: samples.to_vec(),
+        }
+    }
+
+    #[test]
+    fn parse_bench_output() {
+        let timings = parse_output(OUTPUT);
+
+        assert_eq!(timings.len(), 2);
+        assert_eq!(timings["part_one"], 5514.31);
+        assert_eq!(timings["part_two"], 10196.0);
+    }
+
+    #[test]
+    fn critical_values() {
+        assert_eq!(t_critical(4.0), 2.132);
+        // Fractional degrees of freedom round towards the stricter value.
+        assert_eq!(t_critical(4.9), 2.132);
+        assert_eq!(t_critical(0.7), 6.314);
+        assert_eq!(t_critical(250.0), 1.645);
+    }
+
+    #[test]
+    fn regressions() {
+        let base = timing(&[1000.0, 1010.0, 990.0, 1005.0, 995.0]);
+
+        assert!(timing(&[1100.0, 1110.0, 1090.0, 1105.0, 1095.0]).regressed_from(&base, 0.05));
+        // The same mean, but too noisy to tell apart from the baseline.
+        assert!(!timing(&[1100.0, 700.0, 1500.0, 900.0, 1300.0]).regressed_from(&base, 0.05));
+        // Significant, but below the threshold.
+        assert!(!timing(&[1040.0, 1041.0, 1039.0]).regressed_from(&base, 0.05));
+        // A single run can't be tested.
+        assert!(!timing(&[2000.0]).regressed_from(&base, 0.05));
+        assert!(timing(&[800.0, 805.0, 795.0]).improved_from(&base, 0.05));
+    }
+
+    #[test]
+    fn compare_records() {
+        let record = |ns| Record {
+            days: BTreeMap::from([(
+                String::from("2021/15"),
+                BTreeMap::from([(
+                    String::from("part_two"),
+                    timing(&[ns - 10.0, ns, ns + 10.0]),
+                )]),
+            )]),
+        };
+
+        let (table, regressed) = compare(&record(1000.0), &record(1500.0), 0.05);
+        assert!(regressed);
+        assert_eq!(
+            table.text().lines().nth(2),
+            Some("2021/15  part_two   1000 ns  1500 ns  +50.0%  slower")
+        );
+
+        let (table, regressed) = compare(&record(1000.0), &Record::default(), 0.05);
+        assert!(!regressed);
+        assert!(table.is_empty());
+    }
+
+    #[test]
+    fn history_round_trip() {
+        let root = std::env::temp_dir().join(format!("aoc-bench-{}", std::process::id()));
+        let history = History::new(&root);
+        let record = Record {
+            days: BTreeMap::from([(
+                String::from("2021/14"),
+                BTreeMap::from([(String::from("part_one"), timing(&[5514.31, 5602.0]))]),
+            )]),
+        };
+
+        assert_eq!(history.load("abc123").unwrap(), None);
+        history.save("abc123", &record).unwrap();
+        assert_eq!(history.load("abc123").unwrap(), Some(record));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
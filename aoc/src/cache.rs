@@ -0,0 +1,87 @@
+//! Answers of previous runs, keyed by what they were computed from.
+//!
+//! The key hashes the day's release binary together with the input bytes.
+//! The binary changes whenever the day, `common` or the toolchain do, so a
+//! hit is only possible when rerunning would print the same thing.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use sha2::{Digest, Sha256};
+
+use crate::{runner::Answers, Result};
+
+/// Directory holding the cache, relative to the repo root.
+const DIR: &str = "target/aoc/answers";
+
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(root: &Path) -> Self {
+        Cache {
+            dir: root.join(DIR),
+        }
+    }
+
+    pub fn key(binary: &[u8], input: &[u8]) -> String {
+        let mut hasher = Sha256::new();
+        for part in [binary, input] {
+            hasher.update((part.len() as u64).to_le_bytes());
+            hasher.update(part);
+        }
+
+        hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(key).with_extension("answers")
+    }
+
+    pub fn get(&self, key: &str) -> Option<Answers> {
+        let content = fs::read_to_string(self.path(key)).ok()?;
+        Some(Answers::parse(&content))
+    }
+
+    pub fn put(&self, key: &str, answers: &Answers) -> Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.path(key), answers.to_string())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys() {
+        let key = Cache::key(b"binary", b"input");
+
+        assert_eq!(key.len(), 64);
+        assert_eq!(key, Cache::key(b"binary", b"input"));
+        assert_ne!(key, Cache::key(b"binary", b"input\n"));
+        assert_ne!(key, Cache::key(b"binar", b"yinput"));
+    }
+
+    #[test]
+    fn round_trip() {
+        let root = std::env::temp_dir().join(format!("aoc-cache-{}", std::process::id()));
+        let cache = Cache::new(&root);
+        let answers = Answers::parse("Part #1: 4573\nPart #2: 117509\n");
+        let key = Cache::key(b"day 12", b"start-A\n");
+
+        assert_eq!(cache.get(&key), None);
+        cache.put(&key, &answers).unwrap();
+        assert_eq!(cache.get(&key), Some(answers));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
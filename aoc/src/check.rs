@@ -0,0 +1,226 @@
+//! Cross-checking the days against every registered input.
+//!
+//! Inputs live at `<input_dir>/<year>/<day>/<name>.txt`, next to a `<name>.answers`
+//! file holding the answers recorded for them, in the same `Part #N: answer`
+//! format the days print. A day's `sample.TXT` is checked too once it has a
+//! `sample.answers`.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    cache::Cache,
+    runner::{self, Answers},
+    scaffold::Puzzle,
+    Context, Result,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Registered {
+    pub name: String,
+    pub input: PathBuf,
+}
+
+impl Registered {
+    pub fn answers_path(&self) -> PathBuf {
+        self.input.with_extension("answers")
+    }
+
+    pub fn expected(&self) -> Result<Option<Answers>> {
+        match fs::read_to_string(self.answers_path()) {
+            Ok(content) => Ok(Some(Answers::parse(&content))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Inputs registered for `day` under `dir`, sorted by name.
+pub fn registered(dir: &Path, day: Puzzle) -> Result<Vec<Registered>> {
+    let day_dir = day.dir(dir);
+    if !day_dir.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let mut inputs = fs::read_dir(day_dir)?
+        .filter_map(|entry| {
+            let input = entry.ok()?.path();
+            if input.extension()? != "txt" {
+                return None;
+            }
+            let name = input.file_stem()?.to_str()?.to_owned();
+            Some(Registered { name, input })
+        })
+        .collect::<Vec<_>>();
+
+    inputs.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(inputs)
+}
+
+/// The day's own sample, if its answers are known.
+pub fn sample(root: &Path, day: Puzzle) -> Option<Registered> {
+    let sample = Registered {
+        name: String::from("sample"),
+        input: day.dir(root).join("sample.TXT"),
+    };
+
+    (sample.input.is_file() && sample.answers_path().is_file()).then_some(sample)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub part: u8,
+    pub expected: String,
+    pub actual: Option<String>,
+}
+
+/// Parts whose recorded answer differs from the actual one.
+pub fn compare(expected: &Answers, actual: &Answers) -> Vec<Mismatch> {
+    expected
+        .0
+        .iter()
+        .filter(|(part, answer)| actual.get(**part) != Some(answer.as_str()))
+        .map(|(&part, answer)| Mismatch {
+            part,
+            expected: answer.clone(),
+            actual: actual.get(part).map(String::from),
+        })
+        .collect()
+}
+
+/// Runs `days` over all their registered inputs and reports disagreements.
+/// With `record`, inputs without answers get the actual ones saved.
+///
+/// Returns whether every recorded answer matched.
+pub fn check(ctx: &Context, days: &[Puzzle], record: bool, cache: Option<&Cache>) -> Result<bool> {
+    let dir = ctx.root.join(&ctx.config.input_dir);
+    let mut ok = true;
+    let mut checked = 0;
+
+    for &day in days {
+        for input in sample(&ctx.root, day)
+            .into_iter()
+            .chain(registered(&dir, day)?)
+        {
+            checked += 1;
+            let label = format!("{} {}", day, input.name);
+
+            let actual = match runner::run_day(ctx, day, Some(&input.input), cache) {
+                Ok(run) => run.answers,
+                Err(e) => {
+                    println!("{}: {}", label, e);
+                    ok = false;
+                    continue;
+                }
+            };
+
+            let expected = match input.expected()? {
+                Some(expected) => expected,
+                None if record => {
+                    fs::write(input.answers_path(), actual.to_string())?;
+                    println!("{}: recorded", label);
+                    continue;
+                }
+                None => {
+                    println!("{}: no answers recorded", label);
+                    continue;
+                }
+            };
+
+            let mismatches = compare(&expected, &actual);
+            if mismatches.is_empty() {
+                println!("{}: ok", label);
+            }
+
+            for Mismatch {
+                part,
+                expected,
+                actual,
+            } in mismatches
+            {
+                ok = false;
+                let actual = actual.as_deref().unwrap_or("nothing");
+                println!(
+                    "{} part {}: expected {}, got {}",
+                    label, part, expected, actual
+                );
+            }
+        }
+    }
+
+    if checked == 0 {
+        println!("No samples or inputs registered under {}", dir.display());
+    }
+
+    Ok(ok)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scaffold::PuzzleArg;
+
+    #[test]
+    fn compare_answers() {
+        let expected = Answers::parse("Part #1: 37\nPart #2: 168\n");
+        let actual = Answers::parse("Part #1: 37\nPart #2: 170\n");
+
+        assert!(compare(&expected, &expected).is_empty());
+        assert_eq!(
+            compare(&expected, &actual),
+            [Mismatch {
+                part: 2,
+                expected: String::from("168"),
+                actual: Some(String::from("170")),
+            }]
+        );
+    }
+
+    #[test]
+    fn only_recorded_parts_are_compared() {
+        let expected = Answers::parse("Part #2: 168\n");
+        let actual = Answers::parse("Part #1: 1\nPart #2: 168\n");
+
+        assert!(compare(&expected, &actual).is_empty());
+        assert_eq!(compare(&expected, &Answers::default())[0].actual, None);
+    }
+
+    #[test]
+    fn registered_inputs() {
+        let dir = std::env::temp_dir().join(format!("aoc-check-{}", std::process::id()));
+        let day_dir = dir.join("2021/07");
+        fs::create_dir_all(&day_dir).unwrap();
+        for file in ["bob.txt", "alice.txt", "alice.answers", "notes.md"] {
+            fs::write(day_dir.join(file), "").unwrap();
+        }
+
+        let day = |s: &str| s.parse::<PuzzleArg>().unwrap().resolve(2021);
+        let inputs = registered(&dir, day("7")).unwrap();
+        let names = inputs.iter().map(|i| i.name.as_str()).collect::<Vec<_>>();
+
+        assert_eq!(names, ["alice", "bob"]);
+        assert_eq!(inputs[0].expected().unwrap(), Some(Answers::default()));
+        assert_eq!(inputs[1].expected().unwrap(), None);
+        assert!(registered(&dir, day("8")).unwrap().is_empty());
+        assert!(registered(&dir, day("2022/7")).unwrap().is_empty());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn samples_need_answers() {
+        let root = std::env::temp_dir().join(format!("aoc-sample-{}", std::process::id()));
+        let day = "2021/4".parse::<PuzzleArg>().unwrap().resolve(2021);
+        fs::create_dir_all(root.join("2021/04")).unwrap();
+        fs::write(root.join("2021/04/sample.TXT"), "").unwrap();
+
+        assert_eq!(sample(&root, day), None);
+
+        fs::write(root.join("2021/04/sample.answers"), "Part #1: 4512\n").unwrap();
+        assert_eq!(sample(&root, day).unwrap().name, "sample");
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,278 @@
+//! Layered configuration: defaults, then the user-level file, then the
+//! repo-level `.aoc.toml`, then `AOC_*` environment variables.
+
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::Result;
+
+/// Name of the repo-level configuration file, also used to find the repo root.
+pub const REPO_FILE: &str = ".aoc.toml";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Config {
+    /// Session cookie used to download inputs.
+    pub session: Option<String>,
+    /// Year of the days given without one, like `12` rather than `2021/12`.
+    pub year: u16,
+    /// Directory holding additional puzzle inputs, relative to the repo root.
+    pub input_dir: PathBuf,
+    pub output: OutputFormat,
+    pub api_url: String,
+    pub runner: RunnerConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RunnerConfig {
+    /// Seconds a day may take to build and run before it is killed.
+    pub timeout: u64,
+    /// Seconds allowed for downloading an input.
+    pub fetch_timeout: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(format!("Invalid output format {}", s)),
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            session: None,
+            year: 2021,
+            input_dir: PathBuf::from("inputs"),
+            output: OutputFormat::Text,
+            api_url: String::from("https://adventofcode.com"),
+            runner: RunnerConfig {
+                timeout: 300,
+                fetch_timeout: 30,
+            },
+        }
+    }
+}
+
+/// A single configuration source, where every key is optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Layer {
+    session: Option<String>,
+    year: Option<u16>,
+    input_dir: Option<PathBuf>,
+    output: Option<OutputFormat>,
+    api_url: Option<String>,
+    #[serde(default)]
+    runner: RunnerLayer,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RunnerLayer {
+    timeout: Option<u64>,
+    fetch_timeout: Option<u64>,
+}
+
+impl Layer {
+    fn from_file(path: &Path) -> Result<Option<Self>> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(format!("{}: {}", path.display(), e).into()),
+        };
+
+        let layer = toml::from_str(&content).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(Some(layer))
+    }
+
+    fn from_env(var: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        fn parse<T: FromStr>(name: &str, value: Option<String>) -> Result<Option<T>>
+        where
+            T::Err: Display,
+        {
+            value
+                .map(|v| v.parse().map_err(|e| format!("{}: {}", name, e).into()))
+                .transpose()
+        }
+
+        Ok(Layer {
+            session: var("AOC_SESSION").filter(|s| !s.is_empty()),
+            year: parse("AOC_YEAR", var("AOC_YEAR"))?,
+            input_dir: var("AOC_INPUT_DIR").map(PathBuf::from),
+            output: parse("AOC_OUTPUT", var("AOC_OUTPUT"))?,
+            api_url: var("AOC_API_URL"),
+            runner: RunnerLayer {
+                timeout: parse("AOC_TIMEOUT", var("AOC_TIMEOUT"))?,
+                fetch_timeout: parse("AOC_FETCH_TIMEOUT", var("AOC_FETCH_TIMEOUT"))?,
+            },
+        })
+    }
+}
+
+impl Config {
+    /// Loads the effective configuration for the repo at `root`, returning the
+    /// files that contributed to it.
+    pub fn load(root: &Path) -> Result<(Config, Vec<PathBuf>)> {
+        let files = user_file().into_iter().chain([root.join(REPO_FILE)]);
+        let mut config = Config::default();
+        let mut sources = vec![];
+
+        for file in files {
+            if let Some(layer) = Layer::from_file(&file)? {
+                config.apply(layer);
+                sources.push(file);
+            }
+        }
+
+        config.apply(Layer::from_env(|name| std::env::var(name).ok())?);
+
+        Ok((config, sources))
+    }
+
+    fn apply(&mut self, layer: Layer) {
+        let Layer {
+            session,
+            year,
+            input_dir,
+            output,
+            api_url,
+            runner,
+        } = layer;
+
+        self.session = session.or(self.session.take());
+        self.year = year.unwrap_or(self.year);
+        self.input_dir = input_dir.unwrap_or(std::mem::take(&mut self.input_dir));
+        self.output = output.unwrap_or(self.output);
+        self.api_url = api_url.unwrap_or(std::mem::take(&mut self.api_url));
+        self.runner.timeout = runner.timeout.unwrap_or(self.runner.timeout);
+        self.runner.fetch_timeout = runner.fetch_timeout.unwrap_or(self.runner.fetch_timeout);
+    }
+
+    /// The session token, which belongs in the user-level file or the
+    /// environment rather than the committed `.aoc.toml`.
+    pub fn session(&self) -> Result<&str> {
+        self.session.as_deref().ok_or_else(|| {
+            let file = user_file().map_or_else(
+                || String::from("the user configuration file"),
+                |path| path.display().to_string(),
+            );
+            format!("No session token, set `session` in {} or AOC_SESSION", file).into()
+        })
+    }
+
+    /// The configuration as TOML, with secrets redacted.
+    pub fn show(&self) -> String {
+        let mut redacted = self.clone();
+        redacted.session = self.session.as_ref().map(|_| String::from("<redacted>"));
+
+        toml::to_string(&redacted).expect("configuration is always serializable")
+    }
+}
+
+/// `$XDG_CONFIG_HOME/aoc/config.toml`, or the platform equivalent.
+fn user_file() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("aoc").join("config.toml"))
+}
+
+/// Nearest ancestor of the current directory holding a `.aoc.toml` or the
+/// day `.template`.
+pub fn find_root() -> Result<PathBuf> {
+    let cwd = std::env::current_dir()?;
+
+    cwd.ancestors()
+        .find(|dir| dir.join(REPO_FILE).is_file() || dir.join(".template").is_dir())
+        .map(Path::to_path_buf)
+        .ok_or_else(|| "Not inside the advent of code repository".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layer(toml: &str) -> Layer {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn later_layers_take_precedence() {
+        let mut config = Config::default();
+
+        config.apply(layer(
+            r#"
+            session = "user-token"
+            year = 2020
+
+            [runner]
+            timeout = 10
+            "#,
+        ));
+        config.apply(layer("year = 2021\noutput = \"json\""));
+        config.apply(
+            Layer::from_env(|name| match name {
+                "AOC_SESSION" => Some(String::from("env-token")),
+                "AOC_FETCH_TIMEOUT" => Some(String::from("5")),
+                _ => None,
+            })
+            .unwrap(),
+        );
+
+        assert_eq!(config.session.as_deref(), Some("env-token"));
+        assert_eq!(config.year, 2021);
+        assert_eq!(config.output, OutputFormat::Json);
+        assert_eq!(config.runner.timeout, 10);
+        assert_eq!(config.runner.fetch_timeout, 5);
+        assert_eq!(config.api_url, Config::default().api_url);
+    }
+
+    #[test]
+    fn invalid_values() {
+        assert!(toml::from_str::<Layer>("sesion = \"typo\"").is_err());
+        assert!(toml::from_str::<Layer>("output = \"html\"").is_err());
+
+        let err = Layer::from_env(|name| (name == "AOC_YEAR").then(|| String::from("next")));
+        assert_eq!(
+            err.unwrap_err().to_string(),
+            "AOC_YEAR: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn missing_session_points_away_from_repo() {
+        let err = Config::default().session().unwrap_err().to_string();
+
+        assert!(err.contains("AOC_SESSION"));
+        assert!(!err.contains(REPO_FILE));
+    }
+
+    #[test]
+    fn show_redacts_session() {
+        let config = Config {
+            session: Some(String::from("53616c7465645f5f")),
+            ..Config::default()
+        };
+
+        let shown = config.show();
+        assert!(shown.contains("session = \"<redacted>\""));
+        assert!(!shown.contains("53616c"));
+        assert!(shown.contains("year = 2021"));
+        assert!(shown.contains("[runner]"));
+    }
+}
@@ -0,0 +1,408 @@
+//! Analysis of private leaderboard exports, the JSON served at
+//! `{api_url}/{year}/leaderboard/private/view/{id}.json`.
+
+use std::{collections::BTreeMap, fmt::Display, fs, path::Path, str::FromStr};
+
+use serde::Deserialize;
+
+use crate::{table::Table, Result};
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub members: BTreeMap<u64, Member>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u64,
+    /// Stars earned, by day and then part.
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<u8, BTreeMap<u8, Star>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct Star {
+    pub get_star_ts: i64,
+    /// Order in which stars were earned across the whole site, which breaks
+    /// ties between equal timestamps.
+    pub star_index: u64,
+}
+
+/// A star earned by a member.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Solve {
+    pub day: u8,
+    pub part: u8,
+    pub ts: i64,
+}
+
+impl Member {
+    /// The member's name, or how the site shows anonymous users.
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    pub fn star(&self, day: u8, part: u8) -> Option<&Star> {
+        self.completion_day_level.get(&day)?.get(&part)
+    }
+
+    /// Stars in the order they were earned.
+    pub fn timeline(&self) -> Vec<Solve> {
+        let mut solves = self
+            .completion_day_level
+            .iter()
+            .flat_map(|(&day, parts)| {
+                parts.iter().map(move |(&part, star)| Solve {
+                    day,
+                    part,
+                    ts: star.get_star_ts,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        solves.sort_by_key(|solve| (solve.ts, solve.day, solve.part));
+        solves
+    }
+
+    /// Seconds between the two stars of `day`.
+    pub fn delta(&self, day: u8) -> Option<i64> {
+        Some(self.star(day, 2)?.get_star_ts - self.star(day, 1)?.get_star_ts)
+    }
+}
+
+/// How stars are turned into points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scoring {
+    /// The site's rule: among `n` members, the first to get a star earns `n`
+    /// points, the second `n - 1`, and so on.
+    Local,
+    /// One point per star.
+    Stars,
+    /// Like `Local`, but second stars are ranked by the time taken since the
+    /// first one rather than since the puzzle unlocked.
+    Delta,
+}
+
+impl FromStr for Scoring {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "local" => Ok(Scoring::Local),
+            "stars" => Ok(Scoring::Stars),
+            "delta" => Ok(Scoring::Delta),
+            _ => Err(format!(
+                "Invalid scoring {}, expected local, stars or delta",
+                s
+            )),
+        }
+    }
+}
+
+impl Display for Scoring {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let name = match self {
+            Scoring::Local => "local",
+            Scoring::Stars => "stars",
+            Scoring::Delta => "delta",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Self::parse(&json).map_err(|e| format!("{}: {}", path.display(), e).into())
+    }
+
+    pub fn year(&self) -> Result<i64> {
+        self.event
+            .parse()
+            .map_err(|_| format!("Invalid event {}", self.event).into())
+    }
+
+    fn days(&self) -> impl Iterator<Item = u8> + '_ {
+        let last = self
+            .members
+            .values()
+            .filter_map(|m| m.completion_day_level.keys().last())
+            .max()
+            .copied()
+            .unwrap_or(0);
+
+        1..=last
+    }
+
+    /// Points of every member, by id.
+    pub fn scores(&self, scoring: Scoring) -> BTreeMap<u64, u64> {
+        let mut scores: BTreeMap<_, _> = self.members.keys().map(|&id| (id, 0)).collect();
+        if scoring == Scoring::Stars {
+            for member in self.members.values() {
+                *scores.get_mut(&member.id).unwrap() = member.timeline().len() as u64;
+            }
+            return scores;
+        }
+
+        let n = self.members.len() as u64;
+        for day in self.days() {
+            for part in 1..=2 {
+                let mut ranked = self
+                    .members
+                    .values()
+                    .filter_map(|m| {
+                        let star = m.star(day, part)?;
+                        let key = match (scoring, part) {
+                            (Scoring::Delta, 2) => m.delta(day)?,
+                            _ => 0,
+                        };
+                        Some((key, star.get_star_ts, star.star_index, m.id))
+                    })
+                    .collect::<Vec<_>>();
+
+                ranked.sort_unstable();
+                for (rank, (.., id)) in ranked.into_iter().enumerate() {
+                    *scores.get_mut(&id).unwrap() += n - rank as u64;
+                }
+            }
+        }
+
+        scores
+    }
+
+    /// Members with their points, best first. Ties go to whoever has more
+    /// stars, then to whoever got their last star first.
+    pub fn standings(&self, scoring: Scoring) -> Vec<(&Member, u64)> {
+        let scores = self.scores(scoring);
+        let mut standings = self
+            .members
+            .values()
+            .map(|m| (m, scores[&m.id]))
+            .collect::<Vec<_>>();
+
+        standings.sort_by_key(|&(m, score)| {
+            let last = m.timeline().last().map_or(i64::MAX, |s| s.ts);
+            (std::cmp::Reverse((score, m.stars)), last, m.id)
+        });
+        standings
+    }
+
+    pub fn standings_table(&self, scoring: Scoring) -> Table {
+        let mut table = Table::new(["Name", "Score", "Stars"]);
+        for (member, score) in self.standings(scoring) {
+            table.row([
+                member.display_name(),
+                score.to_string(),
+                member.stars.to_string(),
+            ]);
+        }
+        table
+    }
+
+    /// When `member` got each star, as the time since the puzzle unlocked.
+    pub fn timeline_table(&self, member: &Member) -> Result<Table> {
+        let year = self.year()?;
+        let mut table = Table::new(["Day", "Part 1", "Part 2", "Delta"]);
+
+        for &day in member.completion_day_level.keys() {
+            let since_unlock = |part| {
+                member.star(day, part).map_or(String::from("-"), |star| {
+                    clock(star.get_star_ts - unlock(year, day))
+                })
+            };
+
+            table.row([
+                day.to_string(),
+                since_unlock(1),
+                since_unlock(2),
+                member.delta(day).map_or(String::from("-"), clock),
+            ]);
+        }
+
+        Ok(table)
+    }
+
+    /// Time every member took from the first star of a day to the second.
+    pub fn deltas_table(&self) -> Table {
+        let days = self.days().collect::<Vec<_>>();
+        let mut table = Table::new(
+            ["Name".to_owned()]
+                .into_iter()
+                .chain(days.iter().map(u8::to_string)),
+        );
+
+        for (member, _) in self.standings(Scoring::Local) {
+            let deltas = days
+                .iter()
+                .map(|&day| member.delta(day).map_or(String::from("-"), clock));
+            table.row([member.display_name()].into_iter().chain(deltas));
+        }
+
+        table
+    }
+
+    /// Members whose name is `name`, or all of them.
+    pub fn find(&self, name: Option<&str>) -> Result<Vec<&Member>> {
+        let members = self
+            .members
+            .values()
+            .filter(|m| name.is_none_or(|name| m.display_name() == name))
+            .collect::<Vec<_>>();
+
+        match (name, members.is_empty()) {
+            (Some(name), true) => Err(format!("No member named {}", name).into()),
+            _ => Ok(members),
+        }
+    }
+}
+
+/// Unix time at which a puzzle unlocks: midnight EST on December `day`.
+pub fn unlock(year: i64, day: u8) -> i64 {
+    days_from_civil(year, 12, day as i64) * 86400 + 5 * 3600
+}
+
+/// Days from 1970-01-01 to the given date of the proleptic Gregorian
+/// calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146097 + day_of_era - 719468
+}
+
+/// Seconds as `h:mm:ss`.
+fn clock(secs: i64) -> String {
+    format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> Leaderboard {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("fixtures/leaderboard")
+            .join(name);
+        Leaderboard::load(&path).unwrap()
+    }
+
+    fn scores(board: &Leaderboard, scoring: Scoring) -> Vec<(String, u64)> {
+        board
+            .standings(scoring)
+            .into_iter()
+            .map(|(m, score)| (m.display_name(), score))
+            .collect()
+    }
+
+    #[test]
+    fn unlock_times() {
+        assert_eq!(unlock(2021, 1), 1638334800);
+        assert_eq!(unlock(2021, 25), 1640408400);
+        assert_eq!(unlock(2016, 1), 1480568400);
+    }
+
+    #[test]
+    fn local_scores_match_the_site() {
+        for name in ["team.json", "ties.json"] {
+            let board = fixture(name);
+            let scores = board.scores(Scoring::Local);
+
+            for member in board.members.values() {
+                assert_eq!(scores[&member.id], member.local_score, "{}", name);
+            }
+        }
+    }
+
+    #[test]
+    fn alternative_scoring() {
+        let board = fixture("team.json");
+
+        assert_eq!(
+            scores(&board, Scoring::Local),
+            [
+                (String::from("alice"), 9),
+                (String::from("bob"), 9),
+                (String::from("(anonymous user #1003)"), 2),
+            ]
+        );
+        assert_eq!(
+            scores(&board, Scoring::Delta),
+            [
+                (String::from("alice"), 10),
+                (String::from("bob"), 8),
+                (String::from("(anonymous user #1003)"), 2),
+            ]
+        );
+        assert_eq!(scores(&board, Scoring::Stars)[1], (String::from("bob"), 3));
+    }
+
+    #[test]
+    fn timelines_and_deltas() {
+        let board = fixture("team.json");
+        let bob = &board.members[&1002];
+
+        assert_eq!(
+            bob.timeline(),
+            [
+                Solve {
+                    day: 1,
+                    part: 1,
+                    ts: 1638335000
+                },
+                Solve {
+                    day: 1,
+                    part: 2,
+                    ts: 1638335090
+                },
+                Solve {
+                    day: 2,
+                    part: 1,
+                    ts: 1638421700
+                },
+            ]
+        );
+        assert_eq!(bob.delta(1), Some(90));
+        assert_eq!(bob.delta(2), None);
+
+        assert_eq!(
+            board.timeline_table(bob).unwrap().text(),
+            "Day   Part 1   Part 2    Delta\n\
+             ---  -------  -------  -------\n\
+             1    0:03:20  0:04:50  0:01:30\n\
+             2    0:08:20        -        -\n"
+        );
+        assert_eq!(
+            board.deltas_table().markdown(),
+            "| Name                   |       1 |       2 |\n\
+             | :--------------------- | ------: | ------: |\n\
+             | alice                  | 0:00:50 | 0:06:40 |\n\
+             | bob                    | 0:01:30 |       - |\n\
+             | (anonymous user #1003) |       - |       - |\n"
+        );
+    }
+
+    #[test]
+    fn ties_and_empty_members() {
+        let board = fixture("ties.json");
+
+        assert_eq!(board.standings(Scoring::Local)[0].0.display_name(), "dave");
+        assert!(board.members[&2003].timeline().is_empty());
+        assert_eq!(board.find(Some("carol")).unwrap().len(), 1);
+        assert_eq!(board.find(None).unwrap().len(), 3);
+        assert!(board.find(Some("erin")).is_err());
+    }
+}
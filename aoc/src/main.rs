@@ -0,0 +1,373 @@
+//! Command line tooling for the advent of code repository.
+
+mod bench;
+mod cache;
+mod check;
+mod config;
+mod leaderboard;
+mod plugin;
+mod runner;
+mod sample;
+mod scaffold;
+mod serve;
+mod table;
+mod tui;
+
+use std::{error::Error, path::PathBuf, process::exit};
+
+use clap::{Args, Parser, Subcommand};
+
+use cache::Cache;
+use config::{Config, OutputFormat};
+use leaderboard::{Leaderboard, Scoring};
+use scaffold::{Puzzle, PuzzleArg};
+
+pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+#[derive(Parser)]
+#[command(about = "Advent of Code helper")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Recompute answers even if the day and its input are unchanged.
+    #[arg(long, global = true)]
+    no_cache: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Inspect the configuration.
+    #[command(subcommand)]
+    Config(ConfigCommand),
+    /// Create a day from the template and download its input.
+    New { day: PuzzleArg },
+    /// Download a day's input.
+    Fetch { day: PuzzleArg },
+    /// Extract a day's sample and its answers from a saved puzzle page.
+    Sample {
+        day: PuzzleArg,
+        /// Puzzle page saved as HTML.
+        page: PathBuf,
+        /// Code block of the page to use as the sample, counting from 1.
+        #[arg(long, default_value_t = 1)]
+        block: usize,
+        /// Only list the code blocks and emphasised values of the page.
+        #[arg(long)]
+        list: bool,
+    },
+    /// Run days on their own input and print the answers.
+    Run {
+        /// Only run this day.
+        day: Option<PuzzleArg>,
+        /// Run on this input instead.
+        #[arg(long, requires = "day")]
+        input: Option<PathBuf>,
+        /// Also print the intermediate results behind the answers, as text
+        /// or JSON depending on the `output` setting.
+        #[arg(long)]
+        explain: bool,
+        /// Load the days built with `aoc plugin build` instead of running
+        /// their binaries.
+        #[arg(long, conflicts_with = "explain")]
+        plugins: bool,
+    },
+    /// Run days over every input registered in the input directory and
+    /// compare the results with the recorded answers.
+    Check {
+        /// Only check this day.
+        day: Option<PuzzleArg>,
+        /// Save the answers of inputs that have none recorded yet.
+        #[arg(long)]
+        record: bool,
+    },
+    /// Build days as dynamic libraries and inspect them.
+    #[command(subcommand)]
+    Plugin(PluginCommand),
+    /// Record benchmarks and compare them between commits.
+    #[command(subcommand)]
+    Bench(BenchCommand),
+    /// Serve a dashboard of the answers, timings and visualisations of every
+    /// day on localhost.
+    Serve {
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+    },
+    /// Browse and run the days in a full-screen terminal dashboard.
+    Tui,
+    /// Analyse a private leaderboard export.
+    #[command(subcommand)]
+    Leaderboard(LeaderboardCommand),
+}
+
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Print the effective configuration, with secrets redacted.
+    Show,
+}
+
+#[derive(Subcommand)]
+enum PluginCommand {
+    /// Build days as plugins for `aoc run --plugins`.
+    Build {
+        /// Only build this day.
+        day: Option<PuzzleArg>,
+    },
+    /// Show which days have a plugin and whether it loads.
+    List,
+}
+
+#[derive(Subcommand)]
+enum BenchCommand {
+    /// Benchmark days and save the timings under the current commit.
+    Run {
+        /// Only benchmark this day.
+        day: Option<PuzzleArg>,
+        /// Times to run the benchmarks, at least 2 for them to be compared.
+        #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u32).range(2..))]
+        runs: u32,
+    },
+    /// Compare the timings of two commits benchmarked on this machine.
+    Compare {
+        /// Commit or revision to compare against.
+        baseline: String,
+        /// Commit or revision to compare, the current one by default.
+        #[arg(long, default_value = "HEAD")]
+        current: String,
+        /// Smallest slowdown reported, in percent, on top of it being
+        /// statistically significant.
+        #[arg(long, default_value_t = 5.0)]
+        threshold: f64,
+    },
+}
+
+#[derive(Subcommand)]
+enum LeaderboardCommand {
+    /// Rank the members.
+    Standings {
+        #[command(flatten)]
+        board: BoardArgs,
+        /// local, stars or delta.
+        #[arg(long, default_value_t = Scoring::Local)]
+        scoring: Scoring,
+    },
+    /// When each member got their stars.
+    Timeline {
+        #[command(flatten)]
+        board: BoardArgs,
+        /// Only show this member.
+        #[arg(long)]
+        member: Option<String>,
+    },
+    /// Time each member took between the two parts of every day.
+    Deltas {
+        #[command(flatten)]
+        board: BoardArgs,
+    },
+}
+
+#[derive(Args)]
+struct BoardArgs {
+    /// Leaderboard JSON, as saved from the site.
+    file: PathBuf,
+    /// Render Markdown tables.
+    #[arg(long)]
+    markdown: bool,
+}
+
+/// Repository root and the configuration loaded for it.
+#[derive(Clone)]
+pub struct Context {
+    pub root: PathBuf,
+    pub config: Config,
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let root = config::find_root()?;
+    let (config, sources) = Config::load(&root)?;
+    let ctx = Context { root, config };
+    let cache = (!cli.no_cache).then(|| Cache::new(&ctx.root));
+    let year = ctx.config.year;
+
+    match cli.command {
+        Command::Config(ConfigCommand::Show) => {
+            for source in sources {
+                println!("# {}", source.display());
+            }
+            print!("{}", ctx.config.show());
+        }
+        Command::New { day } => scaffold::new_day(&ctx, day.resolve(year))?,
+        Command::Fetch { day } => scaffold::fetch_input(&ctx, day.resolve(year))?,
+        Command::Sample {
+            day,
+            page,
+            block,
+            list,
+        } => {
+            if list {
+                let html = std::fs::read_to_string(&page)?;
+                sample::list(&sample::parse_page(&html));
+            } else {
+                sample::extract(&ctx, day.resolve(year), &page, block)?;
+            }
+        }
+        Command::Run {
+            day,
+            input,
+            explain: true,
+            ..
+        } => {
+            for day in selected(&ctx, day)? {
+                if ctx.config.output == OutputFormat::Text {
+                    println!("{}", day);
+                }
+                print!(
+                    "{}",
+                    runner::explain_day(&ctx, day, input.as_deref(), ctx.config.output)?
+                );
+            }
+        }
+        Command::Run {
+            day,
+            input,
+            explain: false,
+            plugins,
+        } => {
+            for day in selected(&ctx, day)? {
+                let loaded = match plugins {
+                    true => plugin::run_day(&ctx, day, input.as_deref())?,
+                    false => None,
+                };
+                let (run, how) = match loaded {
+                    Some(run) => (run, ", plugin"),
+                    None => (
+                        runner::run_day(&ctx, day, input.as_deref(), cache.as_ref())?,
+                        "",
+                    ),
+                };
+                match run.elapsed {
+                    Some(elapsed) => println!("{} ({:.2?}{})", day, elapsed, how),
+                    None => println!("{} (cached)", day),
+                }
+                print!("{}", run.answers);
+            }
+        }
+        Command::Plugin(PluginCommand::Build { day }) => {
+            for day in selected(&ctx, day)? {
+                let path = plugin::build(&ctx, day)?;
+                println!("{} {}", day, path.display());
+            }
+        }
+        Command::Plugin(PluginCommand::List) => {
+            for day in selected(&ctx, None)? {
+                let path = plugin::path(&ctx, day);
+                if !path.is_file() {
+                    println!("{} -", day);
+                    continue;
+                }
+                match plugin::DayPlugin::load(&path, day) {
+                    Ok(_) => println!("{} {}", day, path.display()),
+                    Err(e) => println!("{} {}", day, e),
+                }
+            }
+        }
+        Command::Check { day, record } => {
+            let days = selected(&ctx, day)?;
+            if !check::check(&ctx, &days, record, cache.as_ref())? {
+                return Err("Some answers disagree with the recorded ones".into());
+            }
+        }
+        Command::Bench(BenchCommand::Run { day, runs }) => {
+            bench::run(&ctx, &selected(&ctx, day)?, runs)?
+        }
+        Command::Bench(BenchCommand::Compare {
+            baseline,
+            current,
+            threshold,
+        }) => {
+            let history = bench::History::new(&ctx.root);
+            let load = |rev: &str| -> Result<bench::Record> {
+                let commit = bench::commit(&ctx.root, rev)?;
+                history
+                    .load(&commit)?
+                    .ok_or_else(|| format!("No benchmarks of {} on this machine", commit).into())
+            };
+
+            let (table, regressed) =
+                bench::compare(&load(&baseline)?, &load(&current)?, threshold / 100.0);
+            if table.is_empty() {
+                println!("No benchmarks in common");
+            } else {
+                print!("{}", table.text());
+            }
+
+            if regressed {
+                return Err("Some benchmarks got slower".into());
+            }
+        }
+        Command::Serve { port } => serve::serve(&ctx, cache.as_ref(), port)?,
+        Command::Tui => tui::run(&ctx)?,
+        Command::Leaderboard(command) => leaderboard(command)?,
+    }
+
+    Ok(())
+}
+
+fn leaderboard(command: LeaderboardCommand) -> Result<()> {
+    let (args, tables) = match command {
+        LeaderboardCommand::Standings { board, scoring } => {
+            let tables = vec![(
+                None,
+                Leaderboard::load(&board.file)?.standings_table(scoring),
+            )];
+            (board, tables)
+        }
+        LeaderboardCommand::Timeline { board, member } => {
+            let leaderboard = Leaderboard::load(&board.file)?;
+            let tables = leaderboard
+                .find(member.as_deref())?
+                .into_iter()
+                .map(|m| Ok((Some(m.display_name()), leaderboard.timeline_table(m)?)))
+                .collect::<Result<Vec<_>>>()?;
+            (board, tables)
+        }
+        LeaderboardCommand::Deltas { board } => {
+            let tables = vec![(None, Leaderboard::load(&board.file)?.deltas_table())];
+            (board, tables)
+        }
+    };
+
+    for (i, (title, table)) in tables.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        match (title, args.markdown) {
+            (Some(title), true) => println!("### {}\n", title),
+            (Some(title), false) => println!("{}\n", title),
+            (None, _) => {}
+        }
+        if table.is_empty() {
+            println!("No stars yet");
+        } else if args.markdown {
+            print!("{}", table.markdown());
+        } else {
+            print!("{}", table.text());
+        }
+    }
+
+    Ok(())
+}
+
+/// `day`, or every day of the configured year.
+fn selected(ctx: &Context, day: Option<PuzzleArg>) -> Result<Vec<Puzzle>> {
+    match day {
+        Some(day) => Ok(vec![day.resolve(ctx.config.year)]),
+        None => runner::days(&ctx.root, ctx.config.year),
+    }
+}
+
+fn main() {
+    if let Err(e) = run(Cli::parse()) {
+        eprintln!("error: {}", e);
+        exit(1);
+    }
+}
@@ -0,0 +1,247 @@
+//! Days loaded as dynamic libraries instead of run as processes, through the
+//! C ABI of `common::plugin`.
+//!
+//! `aoc plugin build` compiles days as `cdylib`s next to their release
+//! binaries. `aoc run --plugins` then loads whichever plugins it finds as they
+//! are, so iterating on a day only means rebuilding that day's plugin.
+
+use std::{
+    ffi::CStr,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    time::Instant,
+};
+
+use common::{
+    input::Input,
+    plugin::{self as abi, Plugin},
+};
+use libloading::{Library, Symbol};
+
+use crate::{
+    runner::{self, Answers, Run},
+    scaffold::Puzzle,
+    Context, Result,
+};
+
+/// Where the plugin of a day is built.
+pub fn path(ctx: &Context, day: Puzzle) -> PathBuf {
+    runner::release_dir(ctx, day).join(format!(
+        "{}solution{}",
+        std::env::consts::DLL_PREFIX,
+        std::env::consts::DLL_SUFFIX
+    ))
+}
+
+/// Builds a day as a `cdylib`, returning the path of the plugin.
+pub fn build(ctx: &Context, day: Puzzle) -> Result<PathBuf> {
+    let mut cmd = Command::new("cargo");
+    cmd.args([
+        "rustc",
+        "--release",
+        "--quiet",
+        "--lib",
+        "--crate-type",
+        "cdylib",
+    ])
+    .current_dir(day.dir(&ctx.root))
+    .stdin(Stdio::null())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped());
+
+    let (success, _, stderr) = runner::wait_with_timeout(cmd.spawn()?, runner::timeout(ctx))
+        .map_err(|e| format!("{}: {}", day, e))?;
+
+    if !success {
+        return Err(format!("{} failed to build: {}", day, runner::failure(&stderr)).into());
+    }
+    Ok(path(ctx, day))
+}
+
+/// A loaded day. The library stays loaded as long as this lives.
+pub struct DayPlugin {
+    plugin: Plugin,
+    _library: Library,
+}
+
+impl DayPlugin {
+    /// Loads the plugin at `path`, checking that it was built for `day` with
+    /// the same ABI and `common` as the runner.
+    pub fn load(path: &Path, day: Puzzle) -> Result<Self> {
+        let error = |e: &dyn std::fmt::Display| format!("{}: {}", path.display(), e);
+
+        // SAFETY: plugins are built from this repository, whose days only
+        // run code from `solve_input` once loaded.
+        let library = unsafe { Library::new(path) }.map_err(|e| error(&e))?;
+
+        let plugin = unsafe {
+            let abi: Symbol<extern "C" fn() -> u32> = library
+                .get(b"aoc_plugin_abi\0")
+                .map_err(|_| error(&"not a day plugin, it exports no aoc_plugin_abi"))?;
+            check_abi(abi())?;
+
+            let describe: Symbol<extern "C" fn() -> Plugin> =
+                library.get(b"aoc_plugin\0").map_err(|e| error(&e))?;
+            describe()
+        };
+
+        let version = unsafe { CStr::from_ptr(plugin.common_version) };
+        check_plugin(&plugin, version, day)?;
+
+        Ok(DayPlugin {
+            plugin,
+            _library: library,
+        })
+    }
+
+    /// What the day prints for `input`.
+    pub fn solve(&self, input: &str) -> Result<String> {
+        // SAFETY: `check_abi` made sure the plugin's `Output` matches ours,
+        // and it is freed exactly once, after being copied.
+        unsafe {
+            let output = (self.plugin.solve)(input.as_ptr(), input.len());
+            let text = output.text();
+            let ok = output.ok;
+            (self.plugin.free)(output);
+
+            if ok {
+                Ok(text)
+            } else {
+                Err(text.into())
+            }
+        }
+    }
+}
+
+fn check_abi(abi: u32) -> Result<()> {
+    if abi != abi::ABI_VERSION {
+        return Err(format!(
+            "plugin ABI version {} doesn't match the runner's {}, rebuild it with `aoc plugin build`",
+            abi,
+            abi::ABI_VERSION
+        )
+        .into());
+    }
+    Ok(())
+}
+
+fn check_plugin(plugin: &Plugin, version: &CStr, day: Puzzle) -> Result<()> {
+    if version != abi::COMMON_VERSION {
+        return Err(format!(
+            "{} plugin was built with common {}, but the runner with {}",
+            day,
+            version.to_string_lossy(),
+            abi::COMMON_VERSION.to_string_lossy()
+        )
+        .into());
+    }
+
+    if (plugin.year, plugin.day) != (day.year, day.day.number()) {
+        return Err(format!("{} plugin is for {}/{:02}", day, plugin.year, plugin.day).into());
+    }
+    Ok(())
+}
+
+/// Runs the plugin of `day` on `input` or its own `input.TXT`, or returns
+/// `None` if the plugin hasn't been built.
+pub fn run_day(ctx: &Context, day: Puzzle, input: Option<&Path>) -> Result<Option<Run>> {
+    let path = path(ctx, day);
+    if !path.is_file() {
+        return Ok(None);
+    }
+
+    let plugin = DayPlugin::load(&path, day)?;
+    let input = runner::input_path(ctx, day, input)?;
+    let input = Input::open(&input).map_err(|e| format!("{}: {}", input.display(), e))?;
+
+    let start = Instant::now();
+    let output = plugin
+        .solve(&input)
+        .map_err(|e| format!("{} failed: {}", day, e))?;
+
+    Ok(Some(Run {
+        answers: Answers::parse(&output),
+        elapsed: Some(start.elapsed()),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::Config, scaffold::PuzzleArg};
+
+    fn puzzle(day: &str) -> Puzzle {
+        day.parse::<PuzzleArg>().unwrap().resolve(2021)
+    }
+
+    fn plugin(day: u8) -> Plugin {
+        extern "C" fn unused(_: *const u8, _: usize) -> abi::Output {
+            unreachable!()
+        }
+
+        Plugin {
+            common_version: abi::COMMON_VERSION.as_ptr(),
+            year: 2021,
+            day,
+            solve: unused,
+            free: abi::free,
+        }
+    }
+
+    #[test]
+    fn checks() {
+        assert!(check_abi(abi::ABI_VERSION).is_ok());
+        assert!(check_abi(abi::ABI_VERSION + 1)
+            .unwrap_err()
+            .to_string()
+            .starts_with("plugin ABI version 2 doesn't match the runner's 1"));
+
+        assert!(check_plugin(&plugin(7), abi::COMMON_VERSION, puzzle("7")).is_ok());
+        assert_eq!(
+            check_plugin(&plugin(7), abi::COMMON_VERSION, puzzle("8"))
+                .unwrap_err()
+                .to_string(),
+            "2021/08 plugin is for 2021/07"
+        );
+        assert_eq!(
+            check_plugin(&plugin(7), c"0.0.1", puzzle("7"))
+                .unwrap_err()
+                .to_string(),
+            format!(
+                "2021/07 plugin was built with common 0.0.1, but the runner with {}",
+                abi::COMMON_VERSION.to_string_lossy()
+            )
+        );
+    }
+
+    #[test]
+    fn load_workspace_plugin() {
+        let ctx = Context {
+            root: Path::new(env!("CARGO_MANIFEST_DIR")).join(".."),
+            config: Config::default(),
+        };
+        let day = puzzle("7");
+
+        let path = build(&ctx, day).unwrap();
+        let plugin = DayPlugin::load(&path, day).unwrap();
+
+        assert_eq!(
+            Answers::parse(&plugin.solve("16,1,2,0,4,2,7,1,2,14").unwrap()),
+            Answers(
+                [(1, String::from("37")), (2, String::from("168"))]
+                    .into_iter()
+                    .collect()
+            )
+        );
+        assert!(plugin.solve("not a number").is_err());
+
+        assert_eq!(
+            DayPlugin::load(&path, puzzle("8"))
+                .err()
+                .unwrap()
+                .to_string(),
+            "2021/08 plugin is for 2021/07"
+        );
+        assert!(DayPlugin::load(&ctx.root.join("README.md"), day).is_err());
+    }
+}
@@ -0,0 +1,456 @@
+//! Building and running the day crates.
+
+use std::{
+    collections::BTreeMap,
+    ffi::OsStr,
+    fmt::Display,
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    cache::Cache,
+    config::OutputFormat,
+    scaffold::{Day, Puzzle},
+    Context, Result,
+};
+
+/// Answers printed by a day, keyed by part number.
+///
+/// Days print one `Part #N: answer` line per part. An empty answer followed
+/// by more lines, like day 13's folded sheet, makes those lines the answer.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers(pub BTreeMap<u8, String>);
+
+impl Answers {
+    pub fn parse(output: &str) -> Self {
+        let mut answers = BTreeMap::new();
+        let mut current = None;
+
+        for line in output.lines() {
+            let part = line
+                .strip_prefix("Part #")
+                .and_then(|rest| rest.split_once(':'))
+                .and_then(|(n, answer)| Some((n.parse::<u8>().ok()?, answer.trim())));
+
+            match (part, current) {
+                (Some((n, answer)), _) => {
+                    answers.insert(n, answer.to_owned());
+                    current = answer.is_empty().then_some(n);
+                }
+                (None, Some(n)) => {
+                    let answer = answers.get_mut(&n).unwrap();
+                    if !answer.is_empty() {
+                        answer.push('\n');
+                    }
+                    answer.push_str(line.trim_end());
+                }
+                (None, None) => {}
+            }
+        }
+
+        Answers(answers)
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        self.0.get(&part).map(String::as_str)
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for (part, answer) in &self.0 {
+            if answer.contains('\n') {
+                writeln!(f, "Part #{}:\n{}", part, answer)?;
+            } else {
+                writeln!(f, "Part #{}: {}", part, answer)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Days of `year` with a crate in the repository, in order.
+pub fn days(root: &Path, year: u16) -> Result<Vec<Puzzle>> {
+    let year_dir = root.join(year.to_string());
+    if !year_dir.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let mut days = fs::read_dir(year_dir)?
+        .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse::<Day>().ok())
+        .map(|day| Puzzle { year, day })
+        .filter(|puzzle| puzzle.dir(root).join("Cargo.toml").is_file())
+        .collect::<Vec<_>>();
+
+    days.sort();
+    Ok(days)
+}
+
+/// Answers of a day, with how long computing them took. Answers served from
+/// the cache have no time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    pub answers: Answers,
+    pub elapsed: Option<Duration>,
+}
+
+/// Builds a day in release mode, returning the path of its binary.
+pub fn build_day(ctx: &Context, day: Puzzle) -> Result<PathBuf> {
+    let dir = day.dir(&ctx.root);
+    let mut cmd = Command::new("cargo");
+    cmd.args(["build", "--release", "--quiet"])
+        .current_dir(&dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let (success, _, stderr) =
+        wait_with_timeout(cmd.spawn()?, timeout(ctx)).map_err(|e| format!("{}: {}", day, e))?;
+
+    if !success {
+        return Err(format!("{} failed to build: {}", day, failure(&stderr)).into());
+    }
+
+    Ok(release_dir(ctx, day).join("solution"))
+}
+
+/// Where cargo puts the release build of a day.
+pub fn release_dir(ctx: &Context, day: Puzzle) -> PathBuf {
+    std::env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| day.dir(&ctx.root).join("target"))
+        .join("release")
+}
+
+/// Absolute path of `input`, or else of the day's own `input.TXT`, which is
+/// not committed and has to be fetched first.
+pub fn input_path(ctx: &Context, day: Puzzle, input: Option<&Path>) -> Result<PathBuf> {
+    if let Some(input) = input {
+        return absolute(input);
+    }
+
+    let input = day.dir(&ctx.root).join("input.TXT");
+    if !input.is_file() {
+        return Err(format!(
+            "{} has no input.TXT, fetch it with `aoc fetch {}`",
+            day, day
+        )
+        .into());
+    }
+    Ok(input)
+}
+
+/// Builds and runs a day in release mode, on `input` or its own `input.TXT`.
+///
+/// With a `cache`, answers for an unchanged binary and input are reused
+/// instead of running the day again.
+pub fn run_day(
+    ctx: &Context,
+    day: Puzzle,
+    input: Option<&Path>,
+    cache: Option<&Cache>,
+) -> Result<Run> {
+    let input = input_path(ctx, day, input)?;
+    let binary = build_day(ctx, day)?;
+
+    let key = match cache {
+        Some(_) => Some(Cache::key(&fs::read(&binary)?, &fs::read(&input)?)),
+        None => None,
+    };
+
+    if let Some(answers) = cache.zip(key.as_deref()).and_then(|(c, key)| c.get(key)) {
+        return Ok(Run {
+            answers,
+            elapsed: None,
+        });
+    }
+
+    let start = Instant::now();
+    let stdout = execute(ctx, day, &binary, &[input.as_os_str()])?;
+    let elapsed = start.elapsed();
+
+    let answers = Answers::parse(&stdout);
+    if let Some((cache, key)) = cache.zip(key) {
+        cache.put(&key, &answers)?;
+    }
+
+    Ok(Run {
+        answers,
+        elapsed: Some(elapsed),
+    })
+}
+
+/// Builds and runs a day with `--explain` on `input` or its own `input.TXT`,
+/// returning what it printed: its answers followed by the explanation, or
+/// only the explanation as JSON.
+pub fn explain_day(
+    ctx: &Context,
+    day: Puzzle,
+    input: Option<&Path>,
+    format: OutputFormat,
+) -> Result<String> {
+    let input = input_path(ctx, day, input)?;
+    let binary = build_day(ctx, day)?;
+    let format = match format {
+        OutputFormat::Text => "--explain",
+        OutputFormat::Json => "--explain=json",
+    };
+
+    execute(ctx, day, &binary, &[OsStr::new(format), input.as_os_str()])
+}
+
+/// Builds and runs a day once with `--explain` on `input` or its own
+/// `input.TXT`, returning its answers along with the text explanation it
+/// printed after them. The time covers explaining as well.
+pub fn run_explained(ctx: &Context, day: Puzzle, input: Option<&Path>) -> Result<(Run, String)> {
+    let input = input_path(ctx, day, input)?;
+    let binary = build_day(ctx, day)?;
+
+    let start = Instant::now();
+    let stdout = execute(
+        ctx,
+        day,
+        &binary,
+        &[OsStr::new("--explain"), input.as_os_str()],
+    )?;
+    let elapsed = start.elapsed();
+
+    let (answers, explanation) = split_explanation(&stdout);
+    let run = Run {
+        answers: Answers::parse(answers),
+        elapsed: Some(elapsed),
+    };
+    Ok((run, explanation.to_owned()))
+}
+
+/// Splits what a day printed with `--explain` into its answers and the
+/// explanation, which starts at the first unindented `name:` line. Answers
+/// spanning several lines never have one, as their lines are drawings.
+pub fn split_explanation(output: &str) -> (&str, &str) {
+    let is_field = |line: &str| {
+        line.split_once(':').is_some_and(|(name, _)| {
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        })
+    };
+
+    let mut offset = 0;
+    for line in output.split_inclusive('\n') {
+        if is_field(line) {
+            return output.split_at(offset);
+        }
+        offset += line.len();
+    }
+    (output, "")
+}
+
+/// Builds and runs a day with `--svg`, returning the drawing it printed.
+/// Days without one fail, as they don't accept the option.
+pub fn svg_day(ctx: &Context, day: Puzzle) -> Result<String> {
+    let binary = build_day(ctx, day)?;
+    let svg = execute(ctx, day, &binary, &[OsStr::new("--svg")])?;
+    if !svg.starts_with("<svg") {
+        return Err(format!("{} printed no drawing", day).into());
+    }
+    Ok(svg)
+}
+
+fn execute(ctx: &Context, day: Puzzle, binary: &Path, args: &[&OsStr]) -> Result<String> {
+    let mut cmd = Command::new(binary);
+    cmd.args(args)
+        .current_dir(day.dir(&ctx.root))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let (success, stdout, stderr) =
+        wait_with_timeout(cmd.spawn()?, timeout(ctx)).map_err(|e| format!("{}: {}", day, e))?;
+
+    if !success {
+        return Err(format!("{} failed: {}", day, failure(&stderr)).into());
+    }
+
+    Ok(stdout)
+}
+
+pub fn timeout(ctx: &Context) -> Duration {
+    Duration::from_secs(ctx.config.runner.timeout)
+}
+
+/// Lines of stderr kept when it holds neither a panic nor a compiler error.
+const TAIL: usize = 5;
+
+/// What went wrong according to the stderr of a failed build or run: the
+/// location and message of a panic, the first compiler error, or else the
+/// last few lines. Rust's hints about backtraces are left out.
+pub fn failure(stderr: &str) -> String {
+    let lines = stderr
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty() && !line.starts_with("note: "))
+        .collect::<Vec<_>>();
+
+    if let Some(i) = lines.iter().position(|line| line.contains(" panicked at ")) {
+        let (_, location) = lines[i].split_once(" panicked at ").unwrap();
+        let message = lines[i + 1..]
+            .iter()
+            .take_while(|line| !line.starts_with("stack backtrace:"))
+            .copied()
+            .collect::<Vec<_>>();
+
+        return if message.is_empty() {
+            format!("panicked at {}", location)
+        } else {
+            format!("panicked at {} {}", location, message.join("\n"))
+        };
+    }
+
+    if let Some(error) = lines.iter().find(|line| line.starts_with("error")) {
+        return error.to_string();
+    }
+
+    match lines.len() {
+        0 => String::from("no output"),
+        n => lines[n.saturating_sub(TAIL)..].join("\n"),
+    }
+}
+
+pub fn absolute(path: &Path) -> Result<PathBuf> {
+    Ok(std::env::current_dir()?.join(path))
+}
+
+/// Waits for `child`, killing it once `timeout` elapses. Returns whether it
+/// succeeded along with everything it wrote.
+pub fn wait_with_timeout(mut child: Child, timeout: Duration) -> Result<(bool, String, String)> {
+    fn drain(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<String> {
+        thread::spawn(move || {
+            let mut buf = String::new();
+            let _ = pipe.read_to_string(&mut buf);
+            buf
+        })
+    }
+
+    let stdout = drain(child.stdout.take().unwrap());
+    let stderr = drain(child.stderr.take().unwrap());
+    let start = Instant::now();
+
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+
+        if start.elapsed() > timeout {
+            child.kill()?;
+            child.wait()?;
+            return Err(format!("timed out after {}s", timeout.as_secs()).into());
+        }
+
+        thread::sleep(Duration::from_millis(20));
+    };
+
+    Ok((
+        status.success(),
+        stdout.join().unwrap_or_default(),
+        stderr.join().unwrap_or_default(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scaffold::PuzzleArg;
+
+    #[test]
+    fn parse_answers() {
+        let answers = Answers::parse("Part #1: 5\nPart #2: 12\n");
+
+        assert_eq!(answers.get(1), Some("5"));
+        assert_eq!(answers.get(2), Some("12"));
+        assert_eq!(answers.get(3), None);
+    }
+
+    #[test]
+    fn split_explanations() {
+        let output = "Part #1: 17\nPart #2:\n▓▓▓\n▓░▓\ndots: 18\npart_one:\n  fold: y=7\n";
+        let (answers, explanation) = split_explanation(output);
+
+        assert_eq!(answers, "Part #1: 17\nPart #2:\n▓▓▓\n▓░▓\n");
+        assert_eq!(explanation, "dots: 18\npart_one:\n  fold: y=7\n");
+        assert_eq!(
+            split_explanation("Part #1: 5\nPart #2: 12\n"),
+            ("Part #1: 5\nPart #2: 12\n", "")
+        );
+    }
+
+    #[test]
+    fn parse_multiline_answer() {
+        let output = "Part #1: 17\nPart #2:\n▓▓▓\n▓░▓ \n▓▓▓\n";
+        let answers = Answers::parse(output);
+
+        assert_eq!(answers.get(1), Some("17"));
+        assert_eq!(answers.get(2), Some("▓▓▓\n▓░▓\n▓▓▓"));
+        assert_eq!(Answers::parse(&answers.to_string()), answers);
+    }
+
+    #[test]
+    fn reports_failures() {
+        let panic = "thread 'main' panicked at src/lib.rs:41:9:\n\
+                     Expected 2 players, found 3\n\
+                     note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace\n";
+        assert_eq!(
+            failure(panic),
+            "panicked at src/lib.rs:41:9: Expected 2 players, found 3"
+        );
+
+        let build = "error[E0308]: mismatched types\n  --> src/lib.rs:3:5\n\n\
+                     error: could not compile `solution` (lib) due to 1 previous error\n";
+        assert_eq!(failure(build), "error[E0308]: mismatched types");
+
+        assert_eq!(failure("a\nb\nc\nd\ne\nf\n"), "b\nc\nd\ne\nf");
+        assert_eq!(failure(""), "no output");
+    }
+
+    #[test]
+    fn kills_on_timeout() {
+        let child = Command::new("sleep")
+            .arg("5")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        let err = wait_with_timeout(child, Duration::from_millis(100)).unwrap_err();
+        assert_eq!(err.to_string(), "timed out after 0s");
+    }
+
+    #[test]
+    fn missing_input() {
+        let root = std::env::temp_dir().join(format!("aoc-runner-{}", std::process::id()));
+        let ctx = Context {
+            root: root.clone(),
+            config: crate::config::Config::default(),
+        };
+        let day = |s: &str| s.parse::<PuzzleArg>().unwrap().resolve(2021);
+        fs::create_dir_all(root.join("2021/07")).unwrap();
+        fs::write(root.join("2021/07/input.TXT"), "16,1,2\n").unwrap();
+
+        assert_eq!(
+            input_path(&ctx, day("7"), None).unwrap(),
+            root.join("2021/07/input.TXT")
+        );
+        assert_eq!(
+            input_path(&ctx, day("18"), None).unwrap_err().to_string(),
+            "2021/18 has no input.TXT, fetch it with `aoc fetch 2021/18`"
+        );
+        assert!(input_path(&ctx, day("18"), Some(Path::new("other.txt"))).is_ok());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,271 @@
+//! Extracting a day's sample from its saved puzzle page.
+//!
+//! The page has one `<article>` per part. Samples are `<pre><code>` blocks,
+//! and the answers for them are emphasised, as a bare `<em>26</em>` or inside
+//! code such as `<code>188 * 24 = <em>4512</em></code>`, usually as the last
+//! emphasised number of the part.
+
+use std::{fs, path::Path};
+
+use crate::{runner::Answers, scaffold::Puzzle, Context, Result};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Article {
+    /// Contents of the `<pre><code>` blocks.
+    pub blocks: Vec<String>,
+    /// Contents of the `<em>` elements outside of the blocks, in order.
+    pub emphasised: Vec<String>,
+}
+
+impl Article {
+    fn parse(html: &str) -> Self {
+        let mut article = Article::default();
+        let mut rest = html;
+
+        while let Some((start, open, close)) = next_element(rest) {
+            // Either tag can have attributes, as in `<em class="star">`.
+            let Some(content) = rest[start + open.len()..]
+                .find('>')
+                .map(|end| start + open.len() + end + 1)
+            else {
+                break;
+            };
+            let after = &rest[content..];
+            let Some(end) = after.find(close) else { break };
+            let text = text(&after[..end]);
+
+            if open == "<pre" {
+                article.blocks.push(text);
+            } else {
+                article.emphasised.push(text);
+            }
+            rest = &after[end + close.len()..];
+        }
+
+        article
+    }
+
+    /// The value most likely to be the sample's answer: the last emphasised
+    /// single word with a digit in it, or else the last single word. Longer
+    /// emphasis is prose, such as the question closing each part.
+    pub fn answer(&self) -> Option<&str> {
+        let words = self
+            .emphasised
+            .iter()
+            .map(|text| text.trim())
+            .filter(|text| !text.is_empty() && !text.contains(char::is_whitespace))
+            .collect::<Vec<_>>();
+
+        words
+            .iter()
+            .rev()
+            .find(|word| word.contains(|c: char| c.is_ascii_digit()))
+            .or(words.last())
+            .copied()
+    }
+}
+
+/// Earliest sample block or emphasis in `html`, with the start of its
+/// opening tag and its closing tag. Emphasis inside a block, such as the
+/// marked numbers of a board, is part of the block.
+fn next_element(html: &str) -> Option<(usize, &'static str, &'static str)> {
+    [("<pre", "</pre>"), ("<em", "</em>")]
+        .into_iter()
+        .filter_map(|(open, close)| Some((find_tag(html, open)?, open, close)))
+        .min_by_key(|&(start, ..)| start)
+}
+
+/// Start of the first `open` tag in `html`, with or without attributes.
+fn find_tag(html: &str, open: &str) -> Option<usize> {
+    html.match_indices(open)
+        .map(|(i, _)| i)
+        .find(|&i| html[i + open.len()..].starts_with(['>', ' ']))
+}
+
+/// Text content of an HTML fragment: tags dropped and entities decoded.
+fn text(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+
+    while let Some(i) = rest.find(['<', '&']) {
+        out.push_str(&rest[..i]);
+        rest = &rest[i..];
+
+        if rest.starts_with('<') {
+            rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+            continue;
+        }
+
+        let entity = rest.find(';').map(|end| (&rest[1..end], end));
+        let decoded = entity.and_then(|(name, end)| {
+            let c = match name {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                _ => {
+                    let code = match name.strip_prefix("#x") {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => name.strip_prefix('#')?.parse().ok()?,
+                    };
+                    char::from_u32(code)?
+                }
+            };
+            Some((c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// The articles of a puzzle page, one per part unlocked when it was saved.
+pub fn parse_page(html: &str) -> Vec<Article> {
+    html.split("<article")
+        .skip(1)
+        .map(|article| Article::parse(article.split("</article>").next().unwrap_or("")))
+        .collect()
+}
+
+/// Lists the code blocks and emphasised values of the page.
+pub fn list(articles: &[Article]) {
+    let mut n = 0;
+    for (part, article) in articles.iter().enumerate() {
+        println!("Part #{}", part + 1);
+
+        for block in &article.blocks {
+            n += 1;
+            let first = block.lines().next().unwrap_or("");
+            let lines = block.lines().count();
+            println!("  block {}: {:?} ({} lines)", n, first, lines);
+        }
+        println!("  emphasised: {}", article.emphasised.join(", "));
+    }
+}
+
+/// Writes block `block` (counting from 1 over the whole page) to the day's
+/// `sample.TXT`, and the detected answers to `sample.answers`.
+pub fn extract(ctx: &Context, day: Puzzle, page: &Path, block: usize) -> Result<()> {
+    let html = fs::read_to_string(page).map_err(|e| format!("{}: {}", page.display(), e))?;
+    let articles = parse_page(&html);
+
+    let sample = articles
+        .iter()
+        .flat_map(|article| &article.blocks)
+        .nth(block.wrapping_sub(1))
+        .ok_or_else(|| format!("{} has no code block {}", page.display(), block))?;
+
+    let answers = Answers(
+        articles
+            .iter()
+            .zip(1..)
+            .filter_map(|(article, part)| Some((part, article.answer()?.to_owned())))
+            .collect(),
+    );
+
+    let dir = day.dir(&ctx.root);
+    fs::write(dir.join("sample.TXT"), sample)?;
+    fs::write(dir.join("sample.answers"), answers.to_string())?;
+
+    println!("Sample saved to {}", dir.join("sample.TXT").display());
+    print!("{}", answers);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Day 4 as the site serves it, with the prose between the samples and
+    /// answers cut short.
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 4: Giant Squid ---</h2><p>You're already almost 1.5km (almost a mile) below the surface of the ocean, already so deep that you can't see any sunlight. What you <em>can</em> see, however, is a giant squid that has attached itself to the outside of your submarine.</p>
+<p>Bingo is played on a set of boards each consisting of a 5x5 grid of numbers. Numbers are chosen at random, and the chosen number is <em>marked</em> on all boards on which it appears. (Numbers may not appear on all boards.) If all numbers in any row or any column of a board are marked, that board <em>wins</em>. (Diagonals don't count.)</p>
+<p>For example, consider the following random numbers and boards:</p>
+<pre><code>7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+</code></pre>
+<p>Finally, <code>24</code> is drawn:</p>
+<pre><code><em>22</em> 13 <em>17</em> <em>11</em>  <em>0</em>
+ 8  <em>2</em> <em>23</em>  <em>4</em> <em>24</em>
+</code></pre>
+<p>At this point, the third board <em>wins</em> because it has at least one complete row or column of marked numbers (in this case, the entire top row is marked: <code><em>14 21 17 24  4</em></code>).</p>
+<p>The <em>score</em> of the winning board can now be calculated. Start by finding the <em>sum of all unmarked numbers</em> on that board; in this case, the sum is <code>188</code>. Then, multiply that sum by <em>the number that was just called</em> when the board won, <code>24</code>, to get the final score, <code>188 * 24 = <em>4512</em></code>.</p>
+<p>To guarantee victory against the giant squid, figure out which board will win first. <em>What will your final score be if you choose that board?</em></p>
+</article>
+<p>Your puzzle answer was <code>27027</code>.</p><article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>On the other hand, it might be wise to try a different strategy: <span title="That's 'cuz a submarine don't pull things' antennas out of their sockets when they lose. Giant squid are known to do that.">let the giant squid win</span>.</p>
+<p>In the above example, the second board is the last to win, which happens after <code>13</code> is eventually called and its middle column is completely marked. If you were to keep playing until this point, the second board would have a sum of unmarked numbers equal to <code>148</code> for a final score of <code>148 * 13 = <em>1924</em></code>.</p>
+<p>Figure out which board will win last. <em>Once it wins, what would its final score be?</em></p>
+</article>
+</main>"#;
+
+    #[test]
+    fn articles() {
+        let articles = parse_page(PAGE);
+
+        assert_eq!(articles.len(), 2);
+        assert_eq!(
+            articles[0].blocks[0],
+            "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1\n\n\
+             22 13 17 11  0\n 8  2 23  4 24\n"
+        );
+        assert_eq!(articles[0].blocks[1], "22 13 17 11  0\n 8  2 23  4 24\n");
+        assert_eq!(
+            articles[0].emphasised,
+            [
+                "can",
+                "marked",
+                "wins",
+                "wins",
+                "14 21 17 24  4",
+                "score",
+                "sum of all unmarked numbers",
+                "the number that was just called",
+                "4512",
+                "What will your final score be if you choose that board?",
+            ]
+        );
+        assert_eq!(articles[0].answer(), Some("4512"));
+        assert_eq!(articles[1].blocks, Vec::<String>::new());
+        assert_eq!(articles[1].answer(), Some("1924"));
+    }
+
+    #[test]
+    fn answers() {
+        let answer = |html: &str| parse_page(html)[0].answer().map(str::to_owned);
+
+        // A bare answer, as on the lanternfish page.
+        assert_eq!(
+            answer("<article><p>a total of <em>26</em> fish. After 80 days, a total of <em>5934</em>.</p><p><em>How many lanternfish would there be after 80 days?</em></p></article>"),
+            Some(String::from("5934"))
+        );
+        // Answers without digits, and escaped blocks.
+        assert_eq!(
+            answer("<article><pre><code>a &lt;-&gt; b</code></pre><p>The top crates are <code><em>CMZ</em></code>.</p></article>"),
+            Some(String::from("CMZ"))
+        );
+        assert_eq!(
+            answer("<article><p>No <em>answer here</em>.</p></article>"),
+            None
+        );
+    }
+
+    #[test]
+    fn entities() {
+        assert_eq!(text("&lt;&#x41;&#66;&gt; &copy; &"), "<AB> &copy; &");
+    }
+}
@@ -0,0 +1,215 @@
+//! Creating new days and downloading their inputs.
+
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    str::FromStr,
+};
+
+use crate::{Context, Result};
+
+/// Puzzle day, between 1 and 25.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Day(u8);
+
+impl Day {
+    pub fn number(&self) -> u8 {
+        self.0
+    }
+}
+
+impl FromStr for Day {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.parse() {
+            Ok(day @ 1..=25) => Ok(Day(day)),
+            _ => Err(format!("Invalid day {}, expected 1 to 25", s)),
+        }
+    }
+}
+
+impl Display for Day {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:02}", self.0)
+    }
+}
+
+/// A day of a given year, whose crate lives at `<year>/<day>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Puzzle {
+    pub year: u16,
+    pub day: Day,
+}
+
+impl Puzzle {
+    /// Crate directory of the day.
+    pub fn dir(&self, root: &Path) -> PathBuf {
+        root.join(self.year.to_string()).join(self.day.to_string())
+    }
+}
+
+impl Display for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}/{}", self.year, self.day)
+    }
+}
+
+/// A day as given on the command line, `12` or `2021/12`. Days without a
+/// year belong to the configured one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PuzzleArg {
+    year: Option<u16>,
+    day: Day,
+}
+
+impl PuzzleArg {
+    pub fn resolve(self, default_year: u16) -> Puzzle {
+        Puzzle {
+            year: self.year.unwrap_or(default_year),
+            day: self.day,
+        }
+    }
+}
+
+impl FromStr for PuzzleArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let Some((year, day)) = s.split_once('/') else {
+            return Ok(PuzzleArg {
+                year: None,
+                day: s.parse()?,
+            });
+        };
+
+        match year.parse() {
+            Ok(year @ 2015..) => Ok(PuzzleArg {
+                year: Some(year),
+                day: day.parse()?,
+            }),
+            _ => Err(format!("Invalid year {}, expected 2015 or later", year)),
+        }
+    }
+}
+
+pub fn new_day(ctx: &Context, puzzle: Puzzle) -> Result<()> {
+    let dir = puzzle.dir(&ctx.root);
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()).into());
+    }
+
+    copy_dir(&ctx.root.join(".template"), &dir)?;
+    let lib = dir.join("src").join("lib.rs");
+    fs::write(&lib, fill_template(&fs::read_to_string(&lib)?, puzzle))?;
+    println!("Created {}", dir.display());
+
+    fetch_input(ctx, puzzle)
+}
+
+/// The template with its `{{year}}` and `{{day}}` placeholders replaced,
+/// the day without a leading zero.
+fn fill_template(template: &str, puzzle: Puzzle) -> String {
+    template
+        .replace("{{year}}", &puzzle.year.to_string())
+        .replace("{{day}}", &puzzle.day.number().to_string())
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to)?;
+
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn fetch_input(ctx: &Context, puzzle: Puzzle) -> Result<()> {
+    let config = &ctx.config;
+    let url = format!(
+        "{}/{}/day/{}/input",
+        config.api_url,
+        puzzle.year,
+        puzzle.day.number()
+    );
+    let target = puzzle.dir(&ctx.root).join("input.TXT");
+
+    let status = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail"])
+        .arg("--max-time")
+        .arg(config.runner.fetch_timeout.to_string())
+        .arg("--cookie")
+        .arg(format!("session={}", config.session()?))
+        .arg("--output")
+        .arg(&target)
+        .arg(&url)
+        .status()?;
+
+    if !status.success() {
+        return Err(format!("Failed to download {}", url).into());
+    }
+
+    println!("Input saved to {}", target.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_day() {
+        assert_eq!("5".parse(), Ok(Day(5)));
+        assert_eq!("05".parse(), Ok(Day(5)));
+        assert_eq!(Day(5).to_string(), "05");
+        assert!("0".parse::<Day>().is_err());
+        assert!("26".parse::<Day>().is_err());
+    }
+
+    #[test]
+    fn parse_puzzle() {
+        let puzzle = |s: &str| s.parse::<PuzzleArg>().map(|arg| arg.resolve(2021));
+
+        assert_eq!(
+            puzzle("2022/1"),
+            Ok(Puzzle {
+                year: 2022,
+                day: Day(1)
+            })
+        );
+        assert_eq!(puzzle("12").unwrap().to_string(), "2021/12");
+        assert_eq!(
+            puzzle("2021/12").unwrap().dir(Path::new("/aoc")),
+            Path::new("/aoc/2021/12")
+        );
+        assert!(puzzle("21/12").is_err());
+        assert!(puzzle("2021/26").is_err());
+        assert!(puzzle("2021/").is_err());
+    }
+
+    #[test]
+    fn fill_placeholders() {
+        let puzzle = Puzzle {
+            year: 2022,
+            day: Day(7),
+        };
+
+        assert_eq!(
+            fill_template(
+                "common::export_plugin!({{year}}, {{day}}, solve_input);",
+                puzzle
+            ),
+            "common::export_plugin!(2022, 7, solve_input);"
+        );
+    }
+}
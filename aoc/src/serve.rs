@@ -0,0 +1,279 @@
+//! Local web dashboard: every day of the configured year with its answers
+//! and timings, and the drawings of the days that print one with `--svg`.
+//!
+//! Pages are plain HTML with the drawings inlined, so the dashboard works
+//! offline.
+
+use std::fmt::Write;
+
+use common::svg::escape;
+use tiny_http::{Header, Server};
+
+use crate::{
+    cache::Cache,
+    runner::{self, Run},
+    scaffold::{Puzzle, PuzzleArg},
+    Context, Result,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Route {
+    Index,
+    Day(Puzzle),
+    Svg(Puzzle),
+}
+
+impl Route {
+    fn parse(url: &str, year: u16) -> Option<Self> {
+        let path = url.split(['?', '#']).next()?.trim_matches('/');
+        if path.is_empty() {
+            return Some(Route::Index);
+        }
+
+        match path.strip_suffix(".svg") {
+            Some(day) => Some(Route::Svg(day.parse::<PuzzleArg>().ok()?.resolve(year))),
+            None => Some(Route::Day(path.parse::<PuzzleArg>().ok()?.resolve(year))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Response {
+    status: u16,
+    content_type: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn html(body: String) -> Self {
+        Response {
+            status: 200,
+            content_type: "text/html; charset=utf-8",
+            body,
+        }
+    }
+
+    fn not_found() -> Self {
+        Response {
+            status: 404,
+            ..Response::html(page("Not found", "<p>No such page.</p>"))
+        }
+    }
+}
+
+/// Serves the dashboard on `127.0.0.1:port` until interrupted.
+pub fn serve(ctx: &Context, cache: Option<&Cache>, port: u16) -> Result<()> {
+    let server = Server::http(("127.0.0.1", port)).map_err(|e| format!("port {}: {}", port, e))?;
+    println!("Serving on http://127.0.0.1:{}", port);
+
+    for request in server.incoming_requests() {
+        let response = respond(ctx, cache, request.url());
+        println!("{} {}", response.status, request.url());
+
+        let header = Header::from_bytes("Content-Type", response.content_type).unwrap();
+        let reply = tiny_http::Response::from_string(response.body)
+            .with_status_code(response.status)
+            .with_header(header);
+        if let Err(e) = request.respond(reply) {
+            eprintln!("error: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+fn respond(ctx: &Context, cache: Option<&Cache>, url: &str) -> Response {
+    let days = match runner::days(&ctx.root, ctx.config.year) {
+        Ok(days) => days,
+        Err(e) => return Response::html(page("Error", &error(&e.to_string()))),
+    };
+    let exists = |day: &Puzzle| days.contains(day);
+
+    match Route::parse(url, ctx.config.year) {
+        Some(Route::Index) => {
+            let runs = days
+                .iter()
+                .map(|&day| (day, run(ctx, day, cache)))
+                .collect::<Vec<_>>();
+            Response::html(index_page(ctx.config.year, &runs))
+        }
+        Some(Route::Day(day)) if exists(&day) => {
+            let svg = runner::svg_day(ctx, day)
+                .map_err(|e| eprintln!("{}", e))
+                .ok();
+            Response::html(day_page(day, &run(ctx, day, cache), svg.as_deref()))
+        }
+        Some(Route::Svg(day)) if exists(&day) => match runner::svg_day(ctx, day) {
+            Ok(svg) => Response {
+                status: 200,
+                content_type: "image/svg+xml",
+                body: svg,
+            },
+            Err(_) => Response::not_found(),
+        },
+        _ => Response::not_found(),
+    }
+}
+
+fn run(ctx: &Context, day: Puzzle, cache: Option<&Cache>) -> std::result::Result<Run, String> {
+    runner::run_day(ctx, day, None, cache).map_err(|e| e.to_string())
+}
+
+fn timing(run: &Run) -> String {
+    match run.elapsed {
+        Some(elapsed) => format!("{:.2?}", elapsed),
+        None => String::from("cached"),
+    }
+}
+
+fn index_page(year: u16, runs: &[(Puzzle, std::result::Result<Run, String>)]) -> String {
+    let mut body =
+        String::from("<table>\n<tr><th>Day</th><th>Part 1</th><th>Part 2</th><th>Time</th></tr>\n");
+
+    for (day, run) in runs {
+        let link = format!(r#"<a href="/{0}">{0}</a>"#, day);
+        match run {
+            Ok(run) => {
+                let part = |n| escape(run.answers.get(n).unwrap_or("-"));
+                writeln!(
+                    body,
+                    "<tr><td>{}</td><td><pre>{}</pre></td><td><pre>{}</pre></td><td>{}</td></tr>",
+                    link,
+                    part(1),
+                    part(2),
+                    timing(run)
+                )
+                .unwrap();
+            }
+            Err(e) => writeln!(
+                body,
+                r#"<tr><td>{}</td><td colspan="3">{}</td></tr>"#,
+                link,
+                error(e)
+            )
+            .unwrap(),
+        }
+    }
+
+    body.push_str("</table>\n");
+    page(&format!("Advent of Code {}", year), &body)
+}
+
+fn day_page(day: Puzzle, run: &std::result::Result<Run, String>, svg: Option<&str>) -> String {
+    let mut body = String::from("<p><a href=\"/\">All days</a></p>\n");
+
+    match run {
+        Ok(run) => {
+            writeln!(body, "<p>Time: {}</p>", timing(run)).unwrap();
+            writeln!(body, "<pre>{}</pre>", escape(&run.answers.to_string())).unwrap();
+        }
+        Err(e) => body.push_str(&error(e)),
+    }
+
+    match svg {
+        Some(svg) => writeln!(body, "<figure>\n{}</figure>", svg).unwrap(),
+        None => body.push_str("<p>No visualisation for this day.</p>\n"),
+    }
+
+    page(&day.to_string(), &body)
+}
+
+fn error(message: &str) -> String {
+    format!("<p class=\"error\">{}</p>\n", escape(message))
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>{0}</title>
+<style>
+body {{ font-family: sans-serif; margin: 2em; }}
+table {{ border-collapse: collapse; }}
+td, th {{ padding: 0.2em 1em; border-bottom: 1px solid #ddd; text-align: left; vertical-align: top; }}
+pre {{ margin: 0; }}
+.error {{ color: #c00; }}
+</style>
+</head>
+<body>
+<h1>{0}</h1>
+{1}</body>
+</html>
+",
+        escape(title),
+        body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, time::Duration};
+
+    use super::*;
+    use crate::{runner::Answers, scaffold::Day};
+
+    fn puzzle(day: u8) -> Puzzle {
+        Puzzle {
+            year: 2021,
+            day: day.to_string().parse::<Day>().unwrap(),
+        }
+    }
+
+    #[test]
+    fn routes() {
+        assert_eq!(Route::parse("/", 2021), Some(Route::Index));
+        assert_eq!(Route::parse("/?refresh", 2021), Some(Route::Index));
+        assert_eq!(Route::parse("/2021/05", 2021), Some(Route::Day(puzzle(5))));
+        assert_eq!(Route::parse("/09", 2021), Some(Route::Day(puzzle(9))));
+        assert_eq!(
+            Route::parse("/2021/13.svg", 2021),
+            Some(Route::Svg(puzzle(13)))
+        );
+        assert_eq!(Route::parse("/favicon.ico", 2021), None);
+        assert_eq!(Route::parse("/2021/26", 2021), None);
+    }
+
+    #[test]
+    fn index() {
+        let answers = Answers(BTreeMap::from([
+            (1, String::from("17")),
+            (2, String::from("#..\n.<#")),
+        ]));
+        let runs = [
+            (
+                puzzle(13),
+                Ok(Run {
+                    answers,
+                    elapsed: Some(Duration::from_micros(1500)),
+                }),
+            ),
+            (puzzle(14), Err(String::from("2021/14 failed: overflow"))),
+        ];
+
+        let html = index_page(2021, &runs);
+
+        assert!(html.contains("<title>Advent of Code 2021</title>"));
+        assert!(html.contains(
+            r#"<tr><td><a href="/2021/13">2021/13</a></td><td><pre>17</pre></td><td><pre>#..
+.&lt;#</pre></td><td>1.50ms</td></tr>"#
+        ));
+        assert!(html.contains(r#"<td colspan="3"><p class="error">2021/14 failed: overflow</p>"#));
+    }
+
+    #[test]
+    fn day() {
+        let run = Ok(Run {
+            answers: Answers(BTreeMap::from([(1, String::from("5"))])),
+            elapsed: None,
+        });
+
+        let html = day_page(puzzle(5), &run, Some("<svg></svg>\n"));
+        assert!(html.contains("<p>Time: cached</p>\n<pre>Part #1: 5\n</pre>"));
+        assert!(html.contains("<figure>\n<svg></svg>\n</figure>"));
+
+        let html = day_page(puzzle(6), &run, None);
+        assert!(html.contains("No visualisation"));
+    }
+}
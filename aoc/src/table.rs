@@ -0,0 +1,129 @@
+//! Tables for reports, rendered for the terminal or as Markdown.
+
+/// Rows of cells under a header. The first column is left-aligned and the
+/// others, usually numbers or times, right-aligned.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Table {
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new<S: Into<String>>(header: impl IntoIterator<Item = S>) -> Self {
+        Table {
+            header: header.into_iter().map(Into::into).collect(),
+            rows: vec![],
+        }
+    }
+
+    pub fn row<S: Into<String>>(&mut self, cells: impl IntoIterator<Item = S>) {
+        self.rows.push(cells.into_iter().map(Into::into).collect());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    fn widths(&self) -> Vec<usize> {
+        let mut widths = vec![0; self.header.len()];
+        for row in [&self.header].into_iter().chain(&self.rows) {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        widths
+    }
+
+    fn line(row: &[String], widths: &[usize], sep: &str) -> String {
+        let cells = widths.iter().enumerate().map(|(i, &width)| {
+            let cell = row.get(i).map(String::as_str).unwrap_or("");
+            if i == 0 {
+                format!("{:<width$}", cell)
+            } else {
+                format!("{:>width$}", cell)
+            }
+        });
+
+        cells.collect::<Vec<_>>().join(sep)
+    }
+
+    /// Columns padded with spaces, with a rule under the header.
+    pub fn text(&self) -> String {
+        let widths = self.widths();
+        let rule = widths.iter().map(|&w| "-".repeat(w)).collect::<Vec<_>>();
+        let mut out = String::new();
+
+        for row in [&self.header, &rule].into_iter().chain(&self.rows) {
+            out.push_str(Self::line(row, &widths, "  ").trim_end());
+            out.push('\n');
+        }
+        out
+    }
+
+    /// A GitHub-flavoured Markdown table.
+    pub fn markdown(&self) -> String {
+        let escape = |row: &Vec<String>| row.iter().map(|c| c.replace('|', "\\|")).collect();
+        let escaped = Table {
+            header: escape(&self.header),
+            rows: self.rows.iter().map(escape).collect(),
+        };
+
+        let widths = escaped
+            .widths()
+            .into_iter()
+            .map(|w| w.max(3))
+            .collect::<Vec<_>>();
+        let rule = widths
+            .iter()
+            .enumerate()
+            .map(|(i, &w)| match i {
+                0 => format!(":{}", "-".repeat(w - 1)),
+                _ => format!("{}:", "-".repeat(w - 1)),
+            })
+            .collect::<Vec<_>>();
+        let mut out = String::new();
+
+        for row in [&escaped.header, &rule].into_iter().chain(&escaped.rows) {
+            out.push_str(&format!("| {} |\n", Self::line(row, &widths, " | ")));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> Table {
+        let mut table = Table::new(["Name", "Score"]);
+        table.row(["alice", "9"]);
+        table.row(["(anonymous user #3)", "12"]);
+        table
+    }
+
+    #[test]
+    fn text() {
+        assert_eq!(
+            table().text(),
+            "Name                 Score\n\
+             -------------------  -----\n\
+             alice                    9\n\
+             (anonymous user #3)     12\n"
+        );
+    }
+
+    #[test]
+    fn markdown() {
+        let mut table = table();
+        table.row(["a|b", "1"]);
+
+        assert_eq!(
+            table.markdown(),
+            "| Name                | Score |\n\
+             | :------------------ | ----: |\n\
+             | alice               |     9 |\n\
+             | (anonymous user #3) |    12 |\n\
+             | a\\|b                |     1 |\n"
+        );
+    }
+}
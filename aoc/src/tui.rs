@@ -0,0 +1,396 @@
+//! Full-screen terminal dashboard: the days of the configured year, with
+//! keys to run them on their sample or their input.
+//!
+//! [`App`] holds the state and reacts to keys, and [`draw`] renders it to any
+//! ratatui backend, so both can be tested without a terminal. Days run on a
+//! thread of their own, so the dashboard keeps redrawing meanwhile.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    sync::{mpsc, Arc},
+    thread,
+    time::Duration,
+};
+
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, List, ListItem, ListState, Paragraph},
+    DefaultTerminal, Frame,
+};
+
+use crate::{
+    runner::{self, Run},
+    scaffold::Puzzle,
+    Context, Result,
+};
+
+const HELP: &str = "↑/↓ select  s run on sample  r run on input  q quit";
+
+/// How long to wait for a key before checking on the running days.
+const POLL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    Sample,
+    Input,
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Source::Sample => write!(f, "sample"),
+            Source::Input => write!(f, "input"),
+        }
+    }
+}
+
+/// Result of the latest run of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    pub source: Source,
+    pub run: std::result::Result<Run, String>,
+    /// What the day printed with `--explain` after its answers.
+    pub state: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Run(Puzzle, Source),
+    Quit,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct App {
+    days: Vec<Puzzle>,
+    selected: usize,
+    outcomes: BTreeMap<Puzzle, Outcome>,
+    running: BTreeSet<Puzzle>,
+    log: Vec<String>,
+}
+
+impl App {
+    pub fn new(days: Vec<Puzzle>) -> Self {
+        App {
+            days,
+            ..App::default()
+        }
+    }
+
+    pub fn selected(&self) -> Option<Puzzle> {
+        self.days.get(self.selected).copied()
+    }
+
+    /// Moves the selection or returns what the key asks for.
+    pub fn key(&mut self, key: KeyCode) -> Option<Action> {
+        match key {
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.days.len().saturating_sub(1))
+            }
+            KeyCode::Char('s') => return Some(Action::Run(self.selected()?, Source::Sample)),
+            KeyCode::Char('r') => return Some(Action::Run(self.selected()?, Source::Input)),
+            KeyCode::Char('q') | KeyCode::Esc => return Some(Action::Quit),
+            _ => {}
+        }
+        None
+    }
+
+    pub fn log(&mut self, message: impl Into<String>) {
+        self.log.push(message.into());
+    }
+
+    /// Marks a day as running, unless it already is.
+    pub fn start(&mut self, day: Puzzle, source: Source) -> bool {
+        if !self.running.insert(day) {
+            self.log(format!("{} is still running", day));
+            return false;
+        }
+        self.log(format!("Running {} on {}", day, source));
+        true
+    }
+
+    pub fn finish(&mut self, day: Puzzle, outcome: Outcome) {
+        self.running.remove(&day);
+        match &outcome.run {
+            Ok(run) => self.log(format!("{} on {}: {}", day, outcome.source, timing(run))),
+            Err(e) => self.log(format!("{} on {} failed: {}", day, outcome.source, e)),
+        }
+        self.outcomes.insert(day, outcome);
+    }
+}
+
+fn timing(run: &Run) -> String {
+    match run.elapsed {
+        Some(elapsed) => format!("{:.2?}", elapsed),
+        None => String::from("cached"),
+    }
+}
+
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [main, help] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+    let [days, panes] =
+        Layout::horizontal([Constraint::Length(14), Constraint::Min(0)]).areas(main);
+    let [answers, state, log] = Layout::vertical([
+        Constraint::Percentage(35),
+        Constraint::Min(0),
+        Constraint::Length(7),
+    ])
+    .areas(panes);
+
+    let items = app.days.iter().map(|day| {
+        let mark = match app.outcomes.get(day).map(|o| &o.run) {
+            _ if app.running.contains(day) => '…',
+            Some(Ok(_)) => '✓',
+            Some(Err(_)) => '✗',
+            None => ' ',
+        };
+        ListItem::new(format!("{} {}", mark, day))
+    });
+    let list = List::new(items)
+        .block(Block::bordered().title("Days"))
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+    let mut list_state = ListState::default().with_selected(Some(app.selected));
+    frame.render_stateful_widget(list, days, &mut list_state);
+
+    let outcome = app.selected().and_then(|day| app.outcomes.get(&day));
+    let (title, answers_text, state_text) = match outcome {
+        Some(outcome) => {
+            let title = format!("Answers on {}", outcome.source);
+            let answers_text = match &outcome.run {
+                Ok(run) => format!("{}Time: {}", run.answers, timing(run)),
+                Err(e) => format!("Failed: {}", e),
+            };
+            let state_text = outcome.state.clone().unwrap_or_default();
+            (title, answers_text, state_text)
+        }
+        None => (
+            String::from("Answers"),
+            String::from("Not run yet"),
+            String::new(),
+        ),
+    };
+    frame.render_widget(
+        Paragraph::new(answers_text).block(Block::bordered().title(title)),
+        answers,
+    );
+    frame.render_widget(
+        Paragraph::new(state_text).block(Block::bordered().title("State")),
+        state,
+    );
+
+    let shown = log.height.saturating_sub(2) as usize;
+    let lines = app.log[app.log.len().saturating_sub(shown)..]
+        .iter()
+        .map(|line| Line::raw(line.as_str()))
+        .collect::<Vec<_>>();
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::bordered().title("Log")),
+        log,
+    );
+
+    frame.render_widget(Paragraph::new(HELP), help);
+}
+
+/// Runs the dashboard until `q` is pressed.
+pub fn run(ctx: &Context) -> Result<()> {
+    let app = App::new(runner::days(&ctx.root, ctx.config.year)?);
+    let mut terminal = ratatui::init();
+    let result = event_loop(Arc::new(ctx.clone()), &mut terminal, app);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(ctx: Arc<Context>, terminal: &mut DefaultTerminal, mut app: App) -> Result<()> {
+    let (sender, finished) = mpsc::channel();
+
+    loop {
+        for (day, outcome) in finished.try_iter() {
+            app.finish(day, outcome);
+        }
+        terminal.draw(|frame| draw(frame, &app))?;
+
+        if !event::poll(POLL)? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match app.key(key.code) {
+            Some(Action::Quit) => return Ok(()),
+            Some(Action::Run(day, source)) if app.start(day, source) => {
+                let (ctx, sender) = (Arc::clone(&ctx), sender.clone());
+                thread::spawn(move || {
+                    // The dashboard may have quit and dropped the receiver.
+                    let _ = sender.send((day, execute(&ctx, day, source)));
+                });
+            }
+            Some(Action::Run(..)) | None => {}
+        }
+    }
+}
+
+/// Runs a day once with `--explain`, so its answers and its explanation come
+/// from the same run.
+fn execute(ctx: &Context, day: Puzzle, source: Source) -> Outcome {
+    let input = match source {
+        Source::Sample => Some(day.dir(&ctx.root).join("sample.TXT")),
+        Source::Input => None,
+    };
+    if input.as_ref().is_some_and(|input| !input.is_file()) {
+        return Outcome {
+            source,
+            run: Err(format!("{} has no sample.TXT", day)),
+            state: None,
+        };
+    }
+
+    match runner::run_explained(ctx, day, input.as_deref()) {
+        Ok((run, explanation)) => Outcome {
+            source,
+            run: Ok(run),
+            state: Some(explanation).filter(|text| !text.is_empty()),
+        },
+        Err(e) => Outcome {
+            source,
+            run: Err(e.to_string()),
+            state: None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use ratatui::{backend::TestBackend, Terminal};
+
+    use super::*;
+    use crate::{runner::Answers, scaffold::PuzzleArg};
+
+    fn puzzle(day: &str) -> Puzzle {
+        day.parse::<PuzzleArg>().unwrap().resolve(2021)
+    }
+
+    fn app() -> App {
+        App::new(vec![puzzle("4"), puzzle("5"), puzzle("13")])
+    }
+
+    fn render(app: &App, width: u16, height: u16) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|frame| draw(frame, app)).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        buffer
+            .content()
+            .chunks(buffer.area.width as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect()
+    }
+
+    #[test]
+    fn keys() {
+        let mut app = app();
+
+        assert_eq!(app.key(KeyCode::Up), None);
+        assert_eq!(app.selected(), Some(puzzle("4")));
+        app.key(KeyCode::Down);
+        app.key(KeyCode::Char('j'));
+        app.key(KeyCode::Down);
+        assert_eq!(app.selected(), Some(puzzle("13")));
+        app.key(KeyCode::Char('k'));
+
+        assert_eq!(
+            app.key(KeyCode::Char('s')),
+            Some(Action::Run(puzzle("5"), Source::Sample))
+        );
+        assert_eq!(
+            app.key(KeyCode::Char('r')),
+            Some(Action::Run(puzzle("5"), Source::Input))
+        );
+        assert_eq!(app.key(KeyCode::Char('q')), Some(Action::Quit));
+        assert_eq!(App::new(vec![]).key(KeyCode::Char('r')), None);
+    }
+
+    #[test]
+    fn first_render() {
+        assert_eq!(
+            render(&app(), 60, 16),
+            [
+                "┌Days────────┐┌Answers─────────────────────────────────────┐",
+                "│  2021/04   ││Not run yet                                 │",
+                "│  2021/05   ││                                            │",
+                "│  2021/13   ││                                            │",
+                "│            │└────────────────────────────────────────────┘",
+                "│            │┌State───────────────────────────────────────┐",
+                "│            ││                                            │",
+                "│            │└────────────────────────────────────────────┘",
+                "│            │┌Log─────────────────────────────────────────┐",
+                "│            ││                                            │",
+                "│            ││                                            │",
+                "│            ││                                            │",
+                "│            ││                                            │",
+                "│            ││                                            │",
+                "└────────────┘└────────────────────────────────────────────┘",
+                "↑/↓ select  s run on sample  r run on input  q quit         ",
+            ]
+        );
+    }
+
+    /// What day 05 prints with `--explain` on its sample.
+    const DAY_05_SAMPLE: &str = "Part #1: 5\nPart #2: 12\n\
+                                 part_one:\n  lines: 6\n  overlaps: 5\n  most_lines_at_a_point: 2\n\
+                                 part_two:\n  lines: 10\n  overlaps: 12\n  most_lines_at_a_point: 3\n";
+
+    #[test]
+    fn render_results() {
+        let mut app = app();
+        app.key(KeyCode::Down);
+        assert!(app.start(puzzle("5"), Source::Sample));
+        assert!(!app.start(puzzle("5"), Source::Input));
+        assert!(render(&app, 60, 20)[2].starts_with("│… 2021/05"));
+
+        let (answers, explanation) = runner::split_explanation(DAY_05_SAMPLE);
+        app.finish(
+            puzzle("5"),
+            Outcome {
+                source: Source::Sample,
+                run: Ok(Run {
+                    answers: Answers::parse(answers),
+                    elapsed: Some(Duration::from_micros(2500)),
+                }),
+                state: Some(explanation.to_owned()),
+            },
+        );
+        app.finish(
+            puzzle("13"),
+            Outcome {
+                source: Source::Input,
+                run: Err(String::from("exit status 101")),
+                state: None,
+            },
+        );
+
+        let screen = render(&app, 60, 30);
+        let text = screen.join("\n");
+
+        assert!(screen[1].starts_with("│  2021/04   ││Part #1: 5 "));
+        assert!(screen[2].starts_with("│✓ 2021/05   ││Part #2: 12 "));
+        assert!(screen[3].starts_with("│✗ 2021/13   ││Time: 2.50ms "));
+        assert!(screen[0].contains("Answers on sample"));
+        assert!(text.contains("│part_one:  "));
+        assert!(text.contains("│  overlaps: 12  "));
+        assert!(text.contains("Running 2021/05 on sample"));
+        assert!(text.contains("2021/05 is still running"));
+        assert!(text.contains("2021/05 on sample: 2.50ms"));
+        assert!(text.contains("2021/13 on input failed: exit status 101"));
+    }
+}
@@ -0,0 +1,176 @@
+//! Arithmetic for calculations that can overflow on some inputs.
+//!
+//! By default these behave like the native operators. The `checked` feature
+//! reports overflow as an [`Overflow`] error instead of panicking (debug) or
+//! silently wrapping (release), and the `wide` feature turns every [`Acc`]
+//! into a 128-bit integer.
+
+use std::{
+    fmt::Display,
+    ops::{Add, Mul, Shl, Sub},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow;
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "arithmetic overflow")
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+pub type Result<T> = std::result::Result<T, Overflow>;
+
+pub trait Int:
+    Copy
+    + Default
+    + PartialOrd
+    + From<u8>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Shl<u32, Output = Self>
+{
+    const BITS: u32;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn leading_zeros(self) -> u32;
+}
+
+macro_rules! impl_int {
+    ($($t:ty),*) => {$(
+        impl Int for $t {
+            const BITS: u32 = <$t>::BITS;
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+
+            fn leading_zeros(self) -> u32 {
+                <$t>::leading_zeros(self)
+            }
+        }
+    )*};
+}
+
+impl_int!(u16, u32, u64, u128, usize, i16, i32, i64, i128, isize);
+
+/// Maps a native integer to the accumulator type used in its place.
+pub trait Widen: Sized {
+    type Output: Int + From<Self>;
+}
+
+macro_rules! impl_widen {
+    ($wide:ty => $($t:ty),*) => {$(
+        impl Widen for $t {
+            #[cfg(not(feature = "wide"))]
+            type Output = $t;
+
+            #[cfg(feature = "wide")]
+            type Output = $wide;
+        }
+    )*};
+}
+
+impl_widen!(u128 => u16, u32, u64, u128);
+impl_widen!(i128 => i16, i32, i64, i128);
+
+/// Accumulator for values that start out as a `T`: `T` itself, or its 128-bit
+/// counterpart with the `wide` feature.
+pub type Acc<T> = <T as Widen>::Output;
+
+pub fn acc<T: Widen>(value: T) -> Acc<T> {
+    value.into()
+}
+
+pub fn add<T: Int>(a: T, b: T) -> Result<T> {
+    if cfg!(feature = "checked") {
+        a.checked_add(b).ok_or(Overflow)
+    } else {
+        Ok(a + b)
+    }
+}
+
+pub fn sub<T: Int>(a: T, b: T) -> Result<T> {
+    if cfg!(feature = "checked") {
+        a.checked_sub(b).ok_or(Overflow)
+    } else {
+        Ok(a - b)
+    }
+}
+
+pub fn mul<T: Int>(a: T, b: T) -> Result<T> {
+    if cfg!(feature = "checked") {
+        a.checked_mul(b).ok_or(Overflow)
+    } else {
+        Ok(a * b)
+    }
+}
+
+/// Shifts `a` left by `bits`. Unlike `checked_shl`, this fails whenever a set
+/// bit would be shifted out, not only when `bits` exceeds the width.
+pub fn shl<T: Int>(a: T, bits: u32) -> Result<T> {
+    if cfg!(feature = "checked") && (bits >= T::BITS || a.leading_zeros() < bits) {
+        return Err(Overflow);
+    }
+
+    Ok(a << bits)
+}
+
+pub fn sum<T: Int>(iter: impl IntoIterator<Item = T>) -> Result<T> {
+    iter.into_iter().try_fold(T::default(), add)
+}
+
+pub fn product<T: Int>(iter: impl IntoIterator<Item = T>) -> Result<T> {
+    iter.into_iter().try_fold(T::from(1), mul)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn in_range() {
+        assert_eq!(add(2u32, 3), Ok(5));
+        assert_eq!(sub(7i64, 9), Ok(-2));
+        assert_eq!(mul(6u16, 7), Ok(42));
+        assert_eq!(shl(0b101u32, 29), Ok(0b101 << 29));
+        assert_eq!(sum([1u64, 2, 3]), Ok(6));
+        assert_eq!(product([2u64, 3, 4]), Ok(24));
+    }
+
+    #[test]
+    fn widening() {
+        let acc = acc(7u32);
+
+        #[cfg(feature = "wide")]
+        assert_eq!(std::mem::size_of_val(&acc), 16);
+
+        #[cfg(not(feature = "wide"))]
+        assert_eq!(std::mem::size_of_val(&acc), 4);
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    fn overflow() {
+        assert_eq!(add(u32::MAX, 1), Err(Overflow));
+        assert_eq!(sub(0u32, 1), Err(Overflow));
+        assert_eq!(mul(i64::MIN, -1), Err(Overflow));
+        assert_eq!(shl(1u64, 64), Err(Overflow));
+        assert_eq!(shl(0xfu64 << 60, 1), Err(Overflow));
+        assert_eq!(sum([u16::MAX, 1]), Err(Overflow));
+        assert_eq!(product([u16::MAX, 2]), Err(Overflow));
+    }
+}
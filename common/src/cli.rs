@@ -0,0 +1,90 @@
+//! Command line of the day binaries: `solution [--explain[=json]] [--svg]
+//! [--frames=dir] [input]`.
+
+use std::{env, process::exit};
+
+use crate::{explain::Format, input::Input};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Args {
+    /// How to print the explanation of the answers, if asked for.
+    pub explain: Option<Format>,
+    /// Print a drawing of the day instead of the answers.
+    pub svg: bool,
+    /// Directory to write a drawing of each step of the day to, instead of
+    /// printing the answers.
+    pub frames: Option<String>,
+    /// Input to run on instead of the bundled one.
+    pub input: Option<String>,
+}
+
+impl Args {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = Args::default();
+
+        for arg in args {
+            match arg.as_str() {
+                "--explain" | "--explain=text" => parsed.explain = Some(Format::Text),
+                "--explain=json" => parsed.explain = Some(Format::Json),
+                "--svg" => parsed.svg = true,
+                _ if arg.starts_with("--frames=") => {
+                    parsed.frames = Some(arg["--frames=".len()..].to_owned())
+                }
+                _ if arg.starts_with("--") => return Err(format!("Unknown option {}", arg)),
+                _ if parsed.input.is_none() => parsed.input = Some(arg),
+                _ => return Err(format!("Unexpected argument {}", arg)),
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    /// Arguments of the running binary, exiting with status 2 if they are
+    /// invalid.
+    pub fn from_env() -> Self {
+        Self::parse(env::args().skip(1)).unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            exit(2)
+        })
+    }
+
+    /// The input file, memory-mapped, if one was given.
+    pub fn read_input(&self) -> Option<Input> {
+        let path = self.input.as_ref()?;
+        Some(Input::open(path).expect("Failed to read input"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Result<Args, String> {
+        Args::parse(list.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(args(&[]), Ok(Args::default()));
+        assert_eq!(
+            args(&["--explain=json", "in.txt"]),
+            Ok(Args {
+                explain: Some(Format::Json),
+                svg: false,
+                frames: None,
+                input: Some(String::from("in.txt")),
+            })
+        );
+        assert_eq!(
+            args(&["in.txt", "--explain"]).unwrap().explain,
+            Some(Format::Text)
+        );
+        assert!(args(&["--svg"]).unwrap().svg);
+        assert_eq!(
+            args(&["--frames=out"]).unwrap().frames.as_deref(),
+            Some("out")
+        );
+        assert!(args(&["--verbose"]).is_err());
+        assert!(args(&["a", "b"]).is_err());
+    }
+}
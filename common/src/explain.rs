@@ -0,0 +1,220 @@
+//! Intermediate results a day can print to show how it got its answers.
+//!
+//! Day binaries take `--explain` for text or `--explain=json` for the same
+//! explanation as a JSON object, see [`crate::cli`].
+//!
+//! ```
+//! use common::explain::Explanation;
+//!
+//! let mut explanation = Explanation::new();
+//! explanation.field("position", 2).field("fuel", 37);
+//!
+//! assert_eq!(explanation.text(), "position: 2\nfuel: 37\n");
+//! assert_eq!(explanation.json(), r#"{"position":2,"fuel":37}"#);
+//! ```
+
+use std::fmt::Write;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Int(i128),
+    Text(String),
+    List(Vec<Value>),
+    Map(Explanation),
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Value {
+            fn from(value: $t) -> Self {
+                Value::Int(value as i128)
+            }
+        }
+    )*};
+}
+
+impl_from_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, i128, isize);
+
+impl From<u128> for Value {
+    fn from(value: u128) -> Self {
+        match i128::try_from(value) {
+            Ok(value) => Value::Int(value),
+            Err(_) => Value::Text(value.to_string()),
+        }
+    }
+}
+
+impl From<char> for Value {
+    fn from(value: char) -> Self {
+        Value::Text(value.to_string())
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::Text(value.to_owned())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::Text(value)
+    }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(values: Vec<T>) -> Self {
+        Value::List(values.into_iter().map(Into::into).collect())
+    }
+}
+
+impl From<Explanation> for Value {
+    fn from(value: Explanation) -> Self {
+        Value::Map(value)
+    }
+}
+
+/// Named values, kept in the order they were added.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Explanation {
+    fields: Vec<(String, Value)>,
+}
+
+impl Explanation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn field(&mut self, name: &str, value: impl Into<Value>) -> &mut Self {
+        self.fields.push((name.to_owned(), value.into()));
+        self
+    }
+
+    /// One `name: value` line per field, with nested values indented below
+    /// their name.
+    pub fn text(&self) -> String {
+        let mut out = String::new();
+        self.write_text(&mut out, 0);
+        out
+    }
+
+    fn write_text(&self, out: &mut String, depth: usize) {
+        for (name, value) in &self.fields {
+            let indent = "  ".repeat(depth);
+            match value {
+                Value::Int(n) => writeln!(out, "{}{}: {}", indent, name, n).unwrap(),
+                Value::Text(s) => writeln!(out, "{}{}: {}", indent, name, s).unwrap(),
+                Value::List(items) => {
+                    writeln!(out, "{}{}:", indent, name).unwrap();
+                    for item in items {
+                        match item {
+                            Value::Int(n) => writeln!(out, "{}  - {}", indent, n).unwrap(),
+                            Value::Text(s) => writeln!(out, "{}  - {}", indent, s).unwrap(),
+                            Value::List(_) => {
+                                writeln!(out, "{}  - {}", indent, item.json()).unwrap()
+                            }
+                            Value::Map(map) => {
+                                writeln!(out, "{}  -", indent).unwrap();
+                                map.write_text(out, depth + 2);
+                            }
+                        }
+                    }
+                }
+                Value::Map(map) => {
+                    writeln!(out, "{}{}:", indent, name).unwrap();
+                    map.write_text(out, depth + 1);
+                }
+            }
+        }
+    }
+
+    pub fn json(&self) -> String {
+        let fields = self
+            .fields
+            .iter()
+            .map(|(name, value)| format!("{}:{}", json_string(name), value.json()));
+        format!("{{{}}}", fields.collect::<Vec<_>>().join(","))
+    }
+}
+
+impl Value {
+    pub fn json(&self) -> String {
+        match self {
+            Value::Int(n) => n.to_string(),
+            Value::Text(s) => json_string(s),
+            Value::List(items) => {
+                let items = items.iter().map(Value::json).collect::<Vec<_>>();
+                format!("[{}]", items.join(","))
+            }
+            Value::Map(map) => map.json(),
+        }
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl Format {
+    pub fn render(&self, explanation: &Explanation) -> String {
+        match self {
+            Format::Text => explanation.text(),
+            Format::Json => explanation.json() + "\n",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Explanation {
+        let mut part = Explanation::new();
+        part.field("element", 'B').field("count", 1749u64);
+
+        let mut entry = Explanation::new();
+        entry.field("output", vec![5, 3]);
+
+        let mut explanation = Explanation::new();
+        explanation
+            .field("most_common", part)
+            .field("entries", vec![Value::Map(entry)])
+            .field("note", "say \"hi\"");
+        explanation
+    }
+
+    #[test]
+    fn text() {
+        assert_eq!(
+            sample().text(),
+            "most_common:\n  element: B\n  count: 1749\nentries:\n  -\n    output:\n      - 5\n      - 3\nnote: say \"hi\"\n"
+        );
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(
+            sample().json(),
+            r#"{"most_common":{"element":"B","count":1749},"entries":[{"output":[5,3]}],"note":"say \"hi\""}"#
+        );
+        assert_eq!(Value::from("a\nb\t").json(), r#""a\nb\u0009""#);
+        assert_eq!(Value::from(u128::MAX).json(), format!("\"{}\"", u128::MAX));
+    }
+}
@@ -0,0 +1,425 @@
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+/// Integer types that can be used as vector components.
+pub trait Scalar:
+    Copy + Default + PartialOrd + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    fn signum(self) -> Self;
+    fn abs(self) -> Self;
+    fn abs_diff(self, other: Self) -> Self;
+}
+
+macro_rules! impl_signed_scalar {
+    ($($t:ty),*) => {$(
+        impl Scalar for $t {
+            fn signum(self) -> Self {
+                <$t>::signum(self)
+            }
+
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+
+            fn abs_diff(self, other: Self) -> Self {
+                (self - other).abs()
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_unsigned_scalar {
+    ($($t:ty),*) => {$(
+        impl Scalar for $t {
+            fn signum(self) -> Self {
+                (self != 0) as $t
+            }
+
+            fn abs(self) -> Self {
+                self
+            }
+
+            fn abs_diff(self, other: Self) -> Self {
+                <$t>::abs_diff(self, other)
+            }
+        }
+    )*};
+}
+
+impl_signed_scalar!(i8, i16, i32, i64, i128, isize);
+impl_unsigned_scalar!(u8, u16, u32, u64, u128, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Vec2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Vec2 { x, y }
+    }
+}
+
+impl<T: Scalar> Vec2<T> {
+    /// Componentwise signum, i.e. the unit step towards `self` on each axis.
+    pub fn signum(self) -> Self {
+        Vec2::new(self.x.signum(), self.y.signum())
+    }
+
+    /// Manhattan length of the vector.
+    pub fn manhattan(self) -> T {
+        self.x.abs() + self.y.abs()
+    }
+
+    /// Manhattan distance between two points.
+    pub fn manhattan_to(self, other: Self) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Componentwise minimum.
+    pub fn min(self, other: Self) -> Self {
+        let min = |a: T, b: T| if b < a { b } else { a };
+        Vec2::new(min(self.x, other.x), min(self.y, other.y))
+    }
+
+    /// Componentwise maximum.
+    pub fn max(self, other: Self) -> Self {
+        let max = |a: T, b: T| if b > a { b } else { a };
+        Vec2::new(max(self.x, other.x), max(self.y, other.y))
+    }
+}
+
+impl Vec2<usize> {
+    /// Orthogonal neighbours that lie within `0..bounds` on both axes.
+    pub fn neighbours(self, bounds: Vec2<usize>) -> impl Iterator<Item = Vec2<usize>> {
+        [(0, -1), (-1, 0), (0, 1), (1, 0)]
+            .into_iter()
+            .filter_map(move |(dx, dy)| {
+                let x = self.x.checked_add_signed(dx)?;
+                let y = self.y.checked_add_signed(dy)?;
+
+                (x < bounds.x && y < bounds.y).then_some(Vec2::new(x, y))
+            })
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vec2<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Vec2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vec2<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Vec2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vec2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Vec2<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Vec2::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Vec2<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Vec2<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T: MulAssign + Copy> MulAssign<T> for Vec2<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        self.x *= rhs;
+        self.y *= rhs;
+    }
+}
+
+impl<T: Display> Display for Vec2<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseVec2Error<E> {
+    MissingSeparator,
+    InvalidComponent(E),
+}
+
+impl<E: Display> Display for ParseVec2Error<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ParseVec2Error::MissingSeparator => write!(f, "expected a `x,y` pair"),
+            ParseVec2Error::InvalidComponent(e) => write!(f, "invalid component: {}", e),
+        }
+    }
+}
+
+impl<E: std::fmt::Debug + Display> std::error::Error for ParseVec2Error<E> {}
+
+/// Parses a vector from the `x,y` notation used across the puzzle inputs.
+impl<T: FromStr> FromStr for Vec2<T> {
+    type Err = ParseVec2Error<T::Err>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x, y) = s.split_once(',').ok_or(ParseVec2Error::MissingSeparator)?;
+        let parse = |s: &str| s.trim().parse().map_err(ParseVec2Error::InvalidComponent);
+
+        Ok(Vec2::new(parse(x)?, parse(y)?))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Vec3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Vec3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Vec3 { x, y, z }
+    }
+}
+
+impl<T: Scalar> Vec3<T> {
+    /// Manhattan length of the vector.
+    pub fn manhattan(self) -> T {
+        self.x.abs() + self.y.abs() + self.z.abs()
+    }
+
+    /// Manhattan distance between two points.
+    pub fn manhattan_to(self, other: Self) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    /// Componentwise minimum.
+    pub fn min(self, other: Self) -> Self {
+        let min = |a: T, b: T| if b < a { b } else { a };
+        Vec3::new(
+            min(self.x, other.x),
+            min(self.y, other.y),
+            min(self.z, other.z),
+        )
+    }
+
+    /// Componentwise maximum.
+    pub fn max(self, other: Self) -> Self {
+        let max = |a: T, b: T| if b > a { b } else { a };
+        Vec3::new(
+            max(self.x, other.x),
+            max(self.y, other.y),
+            max(self.z, other.z),
+        )
+    }
+}
+
+/// Components in `x, y, z` order, for code that picks axes by index.
+impl<T> From<[T; 3]> for Vec3<T> {
+    fn from([x, y, z]: [T; 3]) -> Self {
+        Vec3::new(x, y, z)
+    }
+}
+
+impl<T> From<Vec3<T>> for [T; 3] {
+    fn from(v: Vec3<T>) -> Self {
+        [v.x, v.y, v.z]
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vec3<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Vec3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vec3<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Vec3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vec3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Vec3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Vec3<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Vec3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Vec3<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Vec3<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+    }
+}
+
+impl<T: Display> Display for Vec3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+/// Axis-aligned rectangle, inclusive on both corners.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Aabb<T> {
+    pub min: Vec2<T>,
+    pub max: Vec2<T>,
+}
+
+impl<T> Aabb<T> {
+    pub const fn new(min: Vec2<T>, max: Vec2<T>) -> Self {
+        Aabb { min, max }
+    }
+}
+
+impl<T: Scalar> Aabb<T> {
+    /// Smallest rectangle containing every point, if there is any.
+    pub fn bounding<I: IntoIterator<Item = Vec2<T>>>(points: I) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+
+        Some(points.fold(Aabb::new(first, first), |aabb, p| {
+            Aabb::new(aabb.min.min(p), aabb.max.max(p))
+        }))
+    }
+
+    pub fn contains(&self, p: &Vec2<T>) -> bool {
+        p.x >= self.min.x && p.y >= self.min.y && p.x <= self.max.x && p.y <= self.max.y
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Vec2::new(3, -2);
+        let b = Vec2::new(-1, 5);
+
+        assert_eq!(a + b, Vec2::new(2, 3));
+        assert_eq!(a - b, Vec2::new(4, -7));
+        assert_eq!(-a, Vec2::new(-3, 2));
+        assert_eq!(a * 3, Vec2::new(9, -6));
+
+        let mut c = a;
+        c += b;
+        c -= Vec2::new(1, 1);
+        c *= 2;
+        assert_eq!(c, Vec2::new(2, 4));
+    }
+
+    #[test]
+    fn signum_and_manhattan() {
+        assert_eq!(Vec2::new(-7, 0).signum(), Vec2::new(-1, 0));
+        assert_eq!(Vec2::new(4usize, 0).signum(), Vec2::new(1, 0));
+        assert_eq!(Vec2::new(-3, 4).manhattan(), 7);
+        assert_eq!(Vec2::new(1usize, 8).manhattan_to(Vec2::new(4, 2)), 9);
+    }
+
+    #[test]
+    fn grid_neighbours() {
+        let bounds = Vec2::new(3, 2);
+        let corner = Vec2::new(0usize, 0).neighbours(bounds).collect::<Vec<_>>();
+        let edge = Vec2::new(1usize, 1).neighbours(bounds).collect::<Vec<_>>();
+
+        assert_eq!(corner, [Vec2::new(0, 1), Vec2::new(1, 0)]);
+        assert_eq!(edge, [Vec2::new(1, 0), Vec2::new(0, 1), Vec2::new(2, 1)]);
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("0,9".parse::<Vec2<isize>>(), Ok(Vec2::new(0, 9)));
+        assert_eq!(" 8, -1 ".parse::<Vec2<i32>>(), Ok(Vec2::new(8, -1)));
+        assert_eq!(
+            "8".parse::<Vec2<i32>>(),
+            Err(ParseVec2Error::MissingSeparator)
+        );
+        assert!(matches!(
+            "a,1".parse::<Vec2<u32>>(),
+            Err(ParseVec2Error::InvalidComponent(_))
+        ));
+    }
+
+    #[test]
+    fn vec3() {
+        let a = Vec3::new(1, -2, 3);
+        let b = Vec3::new(-4, 5, 6);
+
+        assert_eq!(a + b, Vec3::new(-3, 3, 9));
+        assert_eq!(a - b, Vec3::new(5, -7, -3));
+        assert_eq!(-a * 2, Vec3::new(-2, 4, -6));
+        assert_eq!(a.min(b), Vec3::new(-4, -2, 3));
+        assert_eq!(a.max(b), Vec3::new(1, 5, 6));
+        assert_eq!(a.manhattan(), 6);
+        assert_eq!(a.manhattan_to(b), 15);
+        assert_eq!(<[i32; 3]>::from(a), [1, -2, 3]);
+        assert_eq!(Vec3::from([1, -2, 3]), a);
+        assert_eq!(a.to_string(), "(1, -2, 3)");
+    }
+
+    #[test]
+    fn aabb() {
+        let area = Aabb::new(Vec2::new(20, -10), Vec2::new(30, -5));
+
+        assert!(area.contains(&Vec2::new(20, -10)));
+        assert!(area.contains(&Vec2::new(30, -5)));
+        assert!(!area.contains(&Vec2::new(31, -5)));
+        assert!(!area.contains(&Vec2::new(25, -11)));
+
+        let points = [Vec2::new(3, 1), Vec2::new(-2, 4), Vec2::new(0, -1)];
+        assert_eq!(
+            Aabb::bounding(points),
+            Some(Aabb::new(Vec2::new(-2, -1), Vec2::new(3, 4)))
+        );
+        assert_eq!(Aabb::<i32>::bounding([]), None);
+    }
+}
@@ -0,0 +1,151 @@
+//! Rectangular grids read in place from the input text, without copying the
+//! rows into nested vectors.
+//!
+//! ```
+//! use common::{geometry::Vec2, grid::Grid};
+//!
+//! let grid = Grid::digits("219\n398\n").unwrap();
+//!
+//! assert_eq!(grid.bounds(), Vec2::new(3, 2));
+//! assert_eq!(grid.digit(Vec2::new(2, 1)), 8);
+//! assert_eq!(grid.row(0), b"219");
+//! ```
+
+use std::ops::Index;
+
+use crate::{
+    geometry::Vec2,
+    parse::{Failure, ParseError},
+};
+
+/// View over the lines of an input that all have the same width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Grid<'a> {
+    bytes: &'a [u8],
+    width: usize,
+    height: usize,
+    /// Distance between the starts of two rows, line ending included.
+    stride: usize,
+}
+
+impl<'a> Grid<'a> {
+    /// Grid of any bytes.
+    pub fn parse(src: &'a str) -> Result<Self, ParseError> {
+        Self::new(src, |_| true, "")
+    }
+
+    /// Grid of single digits, such as height or risk maps.
+    pub fn digits(src: &'a str) -> Result<Self, ParseError> {
+        Self::new(src, |b| b.is_ascii_digit(), "digit")
+    }
+
+    fn new(
+        src: &'a str,
+        valid: impl Fn(u8) -> bool,
+        expected: &'static str,
+    ) -> Result<Self, ParseError> {
+        let fail = |offset, expected| Err(ParseError::new(src, Failure { offset, expected }));
+        let trimmed = src.trim_end();
+        let bytes = trimmed.as_bytes();
+
+        let width = bytes
+            .iter()
+            .position(|&b| b == b'\n' || b == b'\r')
+            .unwrap_or(bytes.len());
+        let stride = match &bytes[width..] {
+            [b'\r', b'\n', ..] => width + 2,
+            _ => width + 1,
+        };
+
+        let mut height = 0;
+        for (y, line) in trimmed.lines().enumerate() {
+            let start = y * stride;
+            if line.as_ptr() as usize - trimmed.as_ptr() as usize != start || line.len() != width {
+                return fail(start + line.len().min(width), "row of the same width");
+            }
+            if let Some(x) = line.bytes().position(|b| !valid(b)) {
+                return fail(start + x, expected);
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            bytes,
+            width,
+            height,
+            stride,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Size of the grid, as accepted by [`Vec2::neighbours`].
+    pub fn bounds(&self) -> Vec2<usize> {
+        Vec2::new(self.width, self.height)
+    }
+
+    pub fn row(&self, y: usize) -> &'a [u8] {
+        let start = y * self.stride;
+        &self.bytes[start..start + self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a [u8]> + '_ {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn get(&self, p: Vec2<usize>) -> Option<u8> {
+        (p.x < self.width && p.y < self.height).then(|| self[p])
+    }
+
+    /// Value of the digit at `p`, for grids made with [`Grid::digits`].
+    pub fn digit(&self, p: Vec2<usize>) -> u8 {
+        self[p] - b'0'
+    }
+}
+
+impl Index<Vec2<usize>> for Grid<'_> {
+    type Output = u8;
+
+    fn index(&self, p: Vec2<usize>) -> &u8 {
+        debug_assert!(p.x < self.width, "{:?} is outside the grid", p);
+        &self.bytes[p.y * self.stride + p.x]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_and_cells() {
+        for src in ["#.#\n..#\n", "#.#\r\n..#", "#.#\n..#\n\n"] {
+            let grid = Grid::parse(src).unwrap();
+
+            assert_eq!(grid.bounds(), Vec2::new(3, 2), "{:?}", src);
+            assert_eq!(grid.rows().collect::<Vec<_>>(), [b"#.#", b"..#"]);
+            assert_eq!(grid[Vec2::new(2, 1)], b'#');
+            assert_eq!(grid.get(Vec2::new(3, 0)), None);
+        }
+
+        assert_eq!(Grid::parse("").unwrap().bounds(), Vec2::new(0, 0));
+    }
+
+    #[test]
+    fn errors() {
+        let err = Grid::parse("123\n45\n678").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.expected, "row of the same width");
+
+        let err = Grid::parse("123\n4567\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 4));
+
+        let err = Grid::digits("123\n4x6\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.expected), (2, 2, "digit"));
+    }
+}
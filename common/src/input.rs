@@ -0,0 +1,73 @@
+//! Puzzle inputs read through a memory map, so that large files are parsed in
+//! place rather than copied into a `String` first.
+
+use std::{fs::File, io, ops::Deref, path::Path};
+
+use memmap2::Mmap;
+
+/// Contents of an input file, checked to be UTF-8 once when opened.
+#[derive(Debug)]
+pub struct Input {
+    /// `None` for empty files, which can't be mapped.
+    map: Option<Mmap>,
+}
+
+impl Input {
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+        if file.metadata()?.len() == 0 {
+            return Ok(Input { map: None });
+        }
+
+        // SAFETY: the file must not change while mapped, which holds for
+        // puzzle inputs as nothing writes to them while a day runs.
+        let map = unsafe { Mmap::map(&file)? };
+        std::str::from_utf8(&map).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        Ok(Input { map: Some(map) })
+    }
+
+    pub fn as_str(&self) -> &str {
+        match &self.map {
+            // SAFETY: checked to be UTF-8 in `open`.
+            Some(map) => unsafe { std::str::from_utf8_unchecked(map) },
+            None => "",
+        }
+    }
+}
+
+impl Deref for Input {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn open() {
+        let dir = std::env::temp_dir().join(format!("common-input-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        fs::write(dir.join("input.txt"), "199\n200\n").unwrap();
+        assert_eq!(&*Input::open(dir.join("input.txt")).unwrap(), "199\n200\n");
+
+        fs::write(dir.join("empty.txt"), "").unwrap();
+        assert_eq!(Input::open(dir.join("empty.txt")).unwrap().as_str(), "");
+
+        fs::write(dir.join("binary.txt"), [0xff, 0xfe]).unwrap();
+        assert_eq!(
+            Input::open(dir.join("binary.txt")).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+        assert!(Input::open(dir.join("missing.txt")).is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,14 @@
+//! Code shared between the daily solutions.
+
+#![feature(internal_output_capture)]
+
+pub mod arith;
+pub mod cli;
+pub mod explain;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod parse;
+pub mod plugin;
+pub mod search;
+pub mod svg;
@@ -0,0 +1,422 @@
+//! Small parser-combinator toolkit for the puzzle input formats.
+//!
+//! Parsers are plain functions from an [`Input`] to a [`PResult`], so any
+//! closure with the right signature can be mixed with the combinators here.
+//! Failures carry the offset they happened at, which [`parse`] turns into a
+//! line and column.
+//!
+//! ```
+//! use common::parse::{self, integer, lines, pair, tag, Parser};
+//!
+//! let moves = lines(pair(integer::<i32>().skip(tag(" -> ")), integer::<i32>()));
+//! assert_eq!(parse::parse(moves, "1 -> 2\n3 -> -4\n"), Ok(vec![(1, 2), (3, -4)]));
+//! ```
+
+use std::{fmt::Display, str::FromStr};
+
+use crate::geometry::{Vec2, Vec3};
+
+/// Remaining input, along with the full source for position tracking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Input<'a> {
+    src: &'a str,
+    offset: usize,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(src: &'a str) -> Self {
+        Input { src, offset: 0 }
+    }
+
+    pub fn rest(&self) -> &'a str {
+        &self.src[self.offset..]
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Splits off the next `n` bytes.
+    pub fn take(self, n: usize) -> (&'a str, Self) {
+        let taken = &self.rest()[..n];
+        let rest = Input {
+            offset: self.offset + n,
+            ..self
+        };
+        (taken, rest)
+    }
+
+    pub fn fail<T>(&self, expected: &'static str) -> PResult<'a, T> {
+        Err(Failure {
+            offset: self.offset,
+            expected,
+        })
+    }
+}
+
+/// Cheap failure value, produced at every backtracking point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Failure {
+    pub offset: usize,
+    pub expected: &'static str,
+}
+
+pub type PResult<'a, T> = Result<(T, Input<'a>), Failure>;
+
+/// Error reported by [`parse`], with a position in the source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: &'static str,
+    /// Text at the error position, up to the end of its line. A `\r` before
+    /// the line break is kept, so stray ones show up in the message.
+    pub found: String,
+}
+
+impl ParseError {
+    pub(crate) fn new(src: &str, failure: Failure) -> Self {
+        let before = &src[..failure.offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before.chars().rev().take_while(|&c| c != '\n').count() + 1;
+        let found = src[failure.offset..].split('\n').next().unwrap_or("");
+
+        ParseError {
+            line,
+            column,
+            expected: failure.expected,
+            found: found.to_owned(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}: expected {}, found {:?}",
+            self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub trait Parser<'a, T> {
+    fn parse(&self, input: Input<'a>) -> PResult<'a, T>;
+
+    fn map<U>(self, f: impl Fn(T) -> U) -> impl Parser<'a, U>
+    where
+        Self: Sized,
+    {
+        move |input| self.parse(input).map(|(v, rest)| (f(v), rest))
+    }
+
+    /// Runs `next` after this parser, keeping only this parser's value.
+    fn skip<U>(self, next: impl Parser<'a, U>) -> impl Parser<'a, T>
+    where
+        Self: Sized,
+    {
+        move |input| {
+            let (v, rest) = self.parse(input)?;
+            let (_, rest) = next.parse(rest)?;
+            Ok((v, rest))
+        }
+    }
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+where
+    F: Fn(Input<'a>) -> PResult<'a, T>,
+{
+    fn parse(&self, input: Input<'a>) -> PResult<'a, T> {
+        self(input)
+    }
+}
+
+/// Runs `parser` over the whole of `src`, allowing only trailing whitespace.
+pub fn parse<'a, T>(parser: impl Parser<'a, T>, src: &'a str) -> Result<T, ParseError> {
+    let (value, rest) = parser
+        .parse(Input::new(src))
+        .map_err(|failure| ParseError::new(src, failure))?;
+
+    if !rest.rest().trim_end().is_empty() {
+        return Err(ParseError::new(
+            src,
+            Failure {
+                offset: rest.offset(),
+                expected: "end of input",
+            },
+        ));
+    }
+
+    Ok(value)
+}
+
+pub fn tag<'a>(expected: &'static str) -> impl Parser<'a, &'a str> {
+    move |input: Input<'a>| {
+        if input.rest().starts_with(expected) {
+            Ok(input.take(expected.len()))
+        } else {
+            input.fail(expected)
+        }
+    }
+}
+
+/// A single character matching `pred`.
+pub fn satisfy<'a>(pred: impl Fn(char) -> bool, expected: &'static str) -> impl Parser<'a, char> {
+    move |input: Input<'a>| match input.rest().chars().next() {
+        Some(c) if pred(c) => Ok((c, input.take(c.len_utf8()).1)),
+        _ => input.fail(expected),
+    }
+}
+
+/// The longest non-empty prefix whose characters all match `pred`.
+pub fn take_while1<'a>(
+    pred: impl Fn(char) -> bool,
+    expected: &'static str,
+) -> impl Parser<'a, &'a str> {
+    move |input: Input<'a>| {
+        let len = input
+            .rest()
+            .find(|c| !pred(c))
+            .unwrap_or(input.rest().len());
+
+        if len == 0 {
+            return input.fail(expected);
+        }
+
+        Ok(input.take(len))
+    }
+}
+
+/// A run of ASCII letters and digits.
+pub fn word<'a>() -> impl Parser<'a, &'a str> {
+    take_while1(|c| c.is_ascii_alphanumeric(), "word")
+}
+
+/// A decimal digit, as its numeric value.
+pub fn digit<'a>() -> impl Parser<'a, u8> {
+    satisfy(|c| c.is_ascii_digit(), "digit").map(|c| c as u8 - b'0')
+}
+
+/// An optionally signed decimal integer.
+pub fn integer<'a, T: FromStr>() -> impl Parser<'a, T> {
+    move |input: Input<'a>| {
+        let rest = input.rest();
+        let sign = rest.starts_with(['-', '+']) as usize;
+        let digits = rest[sign..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len() - sign);
+
+        if digits == 0 {
+            return input.fail("integer");
+        }
+
+        let (num, rest) = input.take(sign + digits);
+        match num.parse() {
+            Ok(num) => Ok((num, rest)),
+            Err(_) => input.fail("integer in range"),
+        }
+    }
+}
+
+/// Integer pair in the `x,y` notation.
+pub fn vec2<'a, T: FromStr>() -> impl Parser<'a, Vec2<T>> {
+    pair(integer().skip(tag(",")), integer()).map(|(x, y)| Vec2::new(x, y))
+}
+
+/// Integer triple in the `x,y,z` notation.
+pub fn vec3<'a, T: FromStr>() -> impl Parser<'a, Vec3<T>> {
+    pair(vec2().skip(tag(",")), integer()).map(|(Vec2 { x, y }, z)| Vec3::new(x, y, z))
+}
+
+pub fn pair<'a, A, B>(a: impl Parser<'a, A>, b: impl Parser<'a, B>) -> impl Parser<'a, (A, B)> {
+    move |input| {
+        let (a, rest) = a.parse(input)?;
+        let (b, rest) = b.parse(rest)?;
+        Ok(((a, b), rest))
+    }
+}
+
+/// Runs `prefix` then `parser`, keeping only the latter's value.
+pub fn preceded<'a, A, T>(
+    prefix: impl Parser<'a, A>,
+    parser: impl Parser<'a, T>,
+) -> impl Parser<'a, T> {
+    move |input| {
+        let (_, rest) = prefix.parse(input)?;
+        parser.parse(rest)
+    }
+}
+
+/// One or more `item`s. Stops before any `sep` that is not followed by an
+/// item, so trailing separators are left for the next parser. An item that
+/// fails after matching part of its input is reported as an error instead.
+pub fn separated<'a, T, S>(
+    item: impl Parser<'a, T>,
+    sep: impl Parser<'a, S>,
+) -> impl Parser<'a, Vec<T>> {
+    move |input| {
+        let (first, mut rest) = item.parse(input)?;
+        let mut items = vec![first];
+
+        while let Ok((_, after_sep)) = sep.parse(rest) {
+            match item.parse(after_sep) {
+                Ok((item, next)) => {
+                    items.push(item);
+                    rest = next;
+                }
+                Err(failure) if failure.offset == after_sep.offset() => break,
+                Err(failure) => return Err(failure),
+            }
+        }
+
+        Ok((items, rest))
+    }
+}
+
+/// One or more `item`s, back to back.
+pub fn many1<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    separated(item, |input| Ok(((), input)))
+}
+
+/// The end of a line, `\n` or `\r\n`.
+pub fn line_break<'a>() -> impl Parser<'a, &'a str> {
+    move |input: Input<'a>| {
+        let rest = input.rest();
+        if rest.starts_with("\r\n") {
+            Ok(input.take(2))
+        } else if rest.starts_with('\n') {
+            Ok(input.take(1))
+        } else {
+            input.fail("line break")
+        }
+    }
+}
+
+/// One `item` per line.
+pub fn lines<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    separated(item, line_break())
+}
+
+/// The blank line between two sections of an input.
+pub fn section_break<'a>() -> impl Parser<'a, &'a str> {
+    move |input: Input<'a>| {
+        let Ok((_, rest)) = pair(line_break(), line_break()).parse(input) else {
+            return input.fail("blank line");
+        };
+
+        Ok(input.take(rest.offset() - input.offset()))
+    }
+}
+
+/// Sections of the same format, separated by blank lines.
+pub fn sections<'a, T>(section: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    separated(section, section_break())
+}
+
+/// Rows of single digits, such as height or risk maps.
+pub fn digit_grid<'a>() -> impl Parser<'a, Vec<Vec<u8>>> {
+    lines(many1(digit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers() {
+        assert_eq!(parse(integer::<i32>(), "-42"), Ok(-42));
+        assert_eq!(parse(integer::<u8>(), "+7\n"), Ok(7));
+        assert_eq!(
+            parse(integer::<u8>(), "256").unwrap_err().expected,
+            "integer in range"
+        );
+        assert_eq!(parse(integer::<u8>(), "-").unwrap_err().expected, "integer");
+    }
+
+    #[test]
+    fn vectors() {
+        assert_eq!(parse(vec2::<i32>(), "-1,2"), Ok(Vec2::new(-1, 2)));
+        assert_eq!(
+            parse(vec3::<i32>(), "404,-588,-901"),
+            Ok(Vec3::new(404, -588, -901))
+        );
+        assert_eq!(parse(vec3::<i32>(), "1,2").unwrap_err().expected, ",");
+    }
+
+    #[test]
+    fn separated_lists() {
+        let nums = || separated(integer::<u32>(), tag(","));
+
+        assert_eq!(parse(nums(), "3,4,3,1,2"), Ok(vec![3, 4, 3, 1, 2]));
+        // The dangling separator is left unconsumed.
+        assert_eq!(parse(nums(), "3,4,").unwrap_err().expected, "end of input");
+    }
+
+    #[test]
+    fn sections_and_lines() {
+        let src = "6,10\n0,14\n\nfold along y=7\nfold along x=5\n";
+        let folds = lines(preceded(
+            tag("fold along "),
+            pair(
+                satisfy(|c| c == 'x' || c == 'y', "axis").skip(tag("=")),
+                integer::<usize>(),
+            ),
+        ));
+        let parser = pair(lines(vec2::<usize>()).skip(section_break()), folds);
+
+        let (points, folds) = parse(parser, src).unwrap();
+        assert_eq!(points, [Vec2::new(6, 10), Vec2::new(0, 14)]);
+        assert_eq!(folds, [('y', 7), ('x', 5)]);
+
+        let boards = sections(lines(many1(preceded(tag("  "), digit()))));
+        assert_eq!(
+            parse(boards, "  1  2\n\n  3"),
+            Ok(vec![vec![vec![1, 2]], vec![vec![3]]])
+        );
+    }
+
+    #[test]
+    fn crlf_lines() {
+        let src = "6,10\r\n0,14\r\n\r\nfold along y=7\r\n";
+        let parser = pair(
+            lines(vec2::<usize>()).skip(section_break()),
+            lines(preceded(tag("fold along y="), integer::<usize>())),
+        );
+
+        assert_eq!(
+            parse(parser, src),
+            Ok((vec![Vec2::new(6, 10), Vec2::new(0, 14)], vec![7]))
+        );
+        assert_eq!(parse(section_break(), "\n\r\n"), Ok("\n\r\n"));
+        assert_eq!(
+            parse(lines(word()), "a\rb").unwrap_err().to_string(),
+            "1:2: expected end of input, found \"\\rb\""
+        );
+        assert_eq!(
+            parse(lines(word()), "ab\r\r\ncd").unwrap_err().found,
+            "\r\r"
+        );
+    }
+
+    #[test]
+    fn digit_grids() {
+        assert_eq!(
+            parse(digit_grid(), "123\n456\n"),
+            Ok(vec![vec![1, 2, 3], vec![4, 5, 6]])
+        );
+    }
+
+    #[test]
+    fn error_positions() {
+        let edges = lines(pair(word().skip(tag("-")), word()));
+        let err = parse(edges, "start-A\nA-c\nb_d\n").unwrap_err();
+
+        assert_eq!((err.line, err.column), (3, 2));
+        assert_eq!(err.expected, "-");
+        assert_eq!(err.found, "_d");
+        assert_eq!(err.to_string(), "3:2: expected -, found \"_d\"");
+    }
+}
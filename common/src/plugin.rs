@@ -0,0 +1,194 @@
+//! C ABI for loading days as dynamic libraries.
+//!
+//! A day built as a `cdylib` exports two functions through [`export_plugin`]:
+//!
+//! - `aoc_plugin_abi() -> u32`, the [`ABI_VERSION`] it was built with. Its
+//!   signature never changes, so loaders can always check it first.
+//! - `aoc_plugin() -> Plugin`, describing the day, only meaningful when the
+//!   ABI versions agree.
+//!
+//! The plugin runs the day's `solve_input` and returns what it printed, so
+//! answers are read the same way as from the day's binary.
+//!
+//! [`export_plugin`]: crate::export_plugin
+
+use std::{
+    any::Any,
+    ffi::{c_char, CStr},
+    io,
+    panic::{self, AssertUnwindSafe},
+    sync::{Arc, Mutex},
+};
+
+/// Layout version of [`Plugin`] and [`Output`], bumped on any change to them.
+pub const ABI_VERSION: u32 = 1;
+
+/// Version of this crate, which plugins and loaders must agree on.
+pub const COMMON_VERSION: &CStr =
+    match CStr::from_bytes_with_nul(concat!(env!("CARGO_PKG_VERSION"), "\0").as_bytes()) {
+        Ok(version) => version,
+        Err(_) => panic!("Invalid crate version"),
+    };
+
+pub type SolveFn = unsafe extern "C" fn(input: *const u8, len: usize) -> Output;
+pub type FreeFn = unsafe extern "C" fn(output: Output);
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct Plugin {
+    pub common_version: *const c_char,
+    pub year: u16,
+    pub day: u8,
+    /// Solves the UTF-8 input, which the caller keeps ownership of.
+    pub solve: SolveFn,
+    /// Releases an [`Output`] returned by `solve`.
+    pub free: FreeFn,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct Output {
+    /// Whether the day succeeded. Otherwise, the text is the error.
+    pub ok: bool,
+    pub text: *mut u8,
+    pub len: usize,
+}
+
+impl Output {
+    fn new(ok: bool, text: String) -> Self {
+        let text = Box::leak(text.into_bytes().into_boxed_slice());
+        Output {
+            ok,
+            text: text.as_mut_ptr(),
+            len: text.len(),
+        }
+    }
+
+    /// Copy of the text.
+    ///
+    /// # Safety
+    ///
+    /// `self` must have been returned by a `solve` function and not freed.
+    pub unsafe fn text(&self) -> String {
+        let bytes = std::slice::from_raw_parts(self.text, self.len);
+        String::from_utf8_lossy(bytes).into_owned()
+    }
+}
+
+/// Runs `solve` on the input, capturing what it prints and any panic.
+///
+/// # Safety
+///
+/// `input` must point to `len` readable bytes.
+pub unsafe fn call(solve: fn(&str), input: *const u8, len: usize) -> Output {
+    let input = std::slice::from_raw_parts(input, len);
+    let Ok(input) = std::str::from_utf8(input) else {
+        return Output::new(false, String::from("Input is not UTF-8"));
+    };
+
+    let buf = Arc::new(Mutex::new(Vec::new()));
+    let previous = io::set_output_capture(Some(buf.clone()));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| solve(input)));
+    io::set_output_capture(previous);
+
+    let printed = String::from_utf8_lossy(&buf.lock().unwrap()).into_owned();
+    match result {
+        Ok(()) => Output::new(true, printed),
+        Err(payload) => Output::new(false, panic_message(payload)),
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => String::from("Panicked"),
+        },
+    }
+}
+
+/// Releases an output of [`call`].
+///
+/// # Safety
+///
+/// `output` must come from [`call`] and not have been freed already.
+pub unsafe extern "C" fn free(output: Output) {
+    drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+        output.text,
+        output.len,
+    )));
+}
+
+/// Exports the C ABI entry points of a day, for its `solve_input` function:
+///
+/// ```ignore
+/// common::export_plugin!(2021, 7, solve_input);
+/// ```
+#[macro_export]
+macro_rules! export_plugin {
+    ($year:expr, $day:expr, $solve:path) => {
+        #[no_mangle]
+        pub extern "C" fn aoc_plugin_abi() -> u32 {
+            $crate::plugin::ABI_VERSION
+        }
+
+        #[no_mangle]
+        pub extern "C" fn aoc_plugin() -> $crate::plugin::Plugin {
+            unsafe extern "C" fn solve(input: *const u8, len: usize) -> $crate::plugin::Output {
+                $crate::plugin::call($solve, input, len)
+            }
+
+            $crate::plugin::Plugin {
+                common_version: $crate::plugin::COMMON_VERSION.as_ptr(),
+                year: $year,
+                day: $day,
+                solve,
+                free: $crate::plugin::free,
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve_input(input: &str) {
+        let n = input.trim().parse::<u32>().expect("Not a number");
+        println!("Part #1: {}", n * 2);
+    }
+
+    crate::export_plugin!(2021, 1, solve_input);
+
+    fn run(input: &str) -> (bool, String) {
+        let plugin = aoc_plugin();
+        unsafe {
+            let output = (plugin.solve)(input.as_ptr(), input.len());
+            let result = (output.ok, output.text());
+            (plugin.free)(output);
+            result
+        }
+    }
+
+    #[test]
+    fn exported_plugin() {
+        let plugin = aoc_plugin();
+
+        assert_eq!(aoc_plugin_abi(), ABI_VERSION);
+        assert_eq!((plugin.year, plugin.day), (2021, 1));
+        assert_eq!(
+            unsafe { CStr::from_ptr(plugin.common_version) },
+            COMMON_VERSION
+        );
+    }
+
+    #[test]
+    fn captured_output_and_panics() {
+        assert_eq!(run("21\n"), (true, String::from("Part #1: 42\n")));
+
+        let (ok, error) = run("x");
+        assert!(!ok);
+        assert!(error.starts_with("Not a number"));
+    }
+}
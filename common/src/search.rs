@@ -0,0 +1,263 @@
+//! Graph searches over implicit graphs.
+//!
+//! Graphs are described by a neighbour function instead of an adjacency list,
+//! so grids and state spaces can be searched without materialising them.
+
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// Outcome of a search: the cost to reach every visited node and the node it
+/// was reached from.
+#[derive(Debug, Clone)]
+pub struct Search<N, C> {
+    pub costs: HashMap<N, C>,
+    pub parents: HashMap<N, N>,
+    /// First node that satisfied the goal predicate, if any.
+    pub goal: Option<N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Search<N, C> {
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.costs.get(node).copied()
+    }
+
+    pub fn goal_cost(&self) -> Option<C> {
+        self.goal.as_ref().and_then(|goal| self.cost(goal))
+    }
+
+    /// Path from the start node to `node`, both included.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.costs.contains_key(node) {
+            return None;
+        }
+
+        let mut path = vec![node.clone()];
+        while let Some(parent) = self.parents.get(path.last().unwrap()) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    pub fn goal_path(&self) -> Option<Vec<N>> {
+        self.goal.as_ref().and_then(|goal| self.path_to(goal))
+    }
+}
+
+/// Breadth-first search, where every edge costs 1.
+///
+/// The search stops at the first node for which `goal` returns true; pass
+/// `|_| false` to explore everything reachable from `start`.
+pub fn bfs<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Search<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search {
+        costs: HashMap::from([(start.clone(), 0)]),
+        parents: HashMap::new(),
+        goal: None,
+    };
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        if goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+
+        let cost = search.costs[&node] + 1;
+        for next in neighbours(&node) {
+            if let Entry::Vacant(entry) = search.costs.entry(next.clone()) {
+                entry.insert(cost);
+                search.parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    search
+}
+
+/// Dijkstra's algorithm, for neighbour functions yielding `(node, edge cost)`.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, neighbours, |_| C::default(), goal)
+}
+
+/// A* search. `heuristic` must never overestimate the remaining cost to the
+/// goal, otherwise the returned cost may not be the minimum.
+pub fn astar<N, C, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut search = Search {
+        costs: HashMap::from([(start.clone(), C::default())]),
+        parents: HashMap::new(),
+        goal: None,
+    };
+
+    // Priority queue of nodes to visit.
+    let mut queue = BinaryHeap::new();
+    queue.push(Queued {
+        priority: heuristic(&start),
+        cost: C::default(),
+        node: start,
+    });
+
+    while let Some(Queued { cost, node, .. }) = queue.pop() {
+        // Not worth visiting, a cheaper path was found after this one was queued.
+        if cost > search.costs[&node] {
+            continue;
+        }
+
+        if goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+
+        for (next, weight) in neighbours(&node) {
+            let next_cost = cost + weight;
+
+            if matches!(search.costs.get(&next), Some(&known) if known <= next_cost) {
+                continue;
+            }
+
+            search.costs.insert(next.clone(), next_cost);
+            search.parents.insert(next.clone(), node.clone());
+            queue.push(Queued {
+                priority: next_cost + heuristic(&next),
+                cost: next_cost,
+                node: next,
+            });
+        }
+    }
+
+    search
+}
+
+/// Queue entry, ordered so the `BinaryHeap` pops the lowest priority first.
+struct Queued<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Queued<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Queued<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 - 1 - 2
+    // |       |
+    // 3 ----- 4 - 5
+    fn graph(node: &u8) -> Vec<u8> {
+        match node {
+            0 => vec![1, 3],
+            1 => vec![0, 2],
+            2 => vec![1, 4],
+            3 => vec![0, 4],
+            4 => vec![2, 3, 5],
+            5 => vec![4],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn bfs_explores_everything() {
+        let search = bfs(0, graph, |_| false);
+
+        assert_eq!(search.costs.len(), 6);
+        assert_eq!(search.cost(&5), Some(3));
+        assert_eq!(search.goal, None);
+    }
+
+    #[test]
+    fn bfs_stops_at_goal() {
+        let search = bfs(0, graph, |&n| n == 4);
+
+        assert_eq!(search.goal_cost(), Some(2));
+        assert_eq!(search.goal_path(), Some(vec![0, 3, 4]));
+        assert!(search.cost(&5).is_none());
+    }
+
+    #[test]
+    fn dijkstra_weighted() {
+        // Going through 3 is shorter in hops but more expensive.
+        let weighted = |&n: &u8| {
+            graph(&n)
+                .into_iter()
+                .map(move |m| (m, if n == 3 || m == 3 { 10 } else { 1 }))
+        };
+
+        let search = dijkstra(0, weighted, |&n| n == 5);
+
+        assert_eq!(search.goal_cost(), Some(4u32));
+        assert_eq!(search.goal_path(), Some(vec![0, 1, 2, 4, 5]));
+    }
+
+    #[test]
+    fn astar_on_grid() {
+        let size = 10i32;
+        let wall = |x: i32, y: i32| x == 5 && y < 8;
+        let neighbours = |&(x, y): &(i32, i32)| {
+            [(0, 1), (1, 0), (0, -1), (-1, 0)]
+                .into_iter()
+                .map(move |(dx, dy)| (x + dx, y + dy))
+                .filter(|&(x, y)| (0..size).contains(&x) && (0..size).contains(&y) && !wall(x, y))
+                .map(|n| (n, 1))
+        };
+        let target = (9, 0);
+        let manhattan = |&(x, y): &(i32, i32)| (target.0 - x).abs() + (target.1 - y).abs();
+
+        let astar = astar((0, 0), neighbours, manhattan, |&n| n == target);
+        let dijkstra = dijkstra((0, 0), neighbours, |&n| n == target);
+
+        assert_eq!(astar.goal_cost(), Some(9 + 16));
+        assert_eq!(astar.goal_cost(), dijkstra.goal_cost());
+        assert!(astar.costs.len() <= dijkstra.costs.len());
+    }
+}
@@ -0,0 +1,232 @@
+//! Minimal SVG documents, for days to draw their state without any external
+//! assets.
+//!
+//! ```
+//! use common::svg::Svg;
+//!
+//! let mut svg = Svg::new(20.0, 10.0);
+//! svg.rect(0.0, 0.0, 10.0, 10.0, "#000");
+//!
+//! assert!(svg.to_string().contains(r##"<rect x="0" y="0" width="10" height="10" fill="#000"/>"##));
+//! ```
+
+use std::{
+    fmt::{Display, Write},
+    fs, io,
+    path::Path,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Svg {
+    width: f64,
+    height: f64,
+    body: String,
+}
+
+impl Svg {
+    pub fn new(width: f64, height: f64) -> Self {
+        Svg {
+            width,
+            height,
+            body: String::new(),
+        }
+    }
+
+    pub fn rect(&mut self, x: f64, y: f64, width: f64, height: f64, fill: &str) -> &mut Self {
+        writeln!(
+            self.body,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+            n(x),
+            n(y),
+            n(width),
+            n(height),
+            fill
+        )
+        .unwrap();
+        self
+    }
+
+    pub fn line(
+        &mut self,
+        from: (f64, f64),
+        to: (f64, f64),
+        stroke: &str,
+        width: f64,
+    ) -> &mut Self {
+        writeln!(
+            self.body,
+            r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="{}"/>"#,
+            n(from.0),
+            n(from.1),
+            n(to.0),
+            n(to.1),
+            stroke,
+            n(width)
+        )
+        .unwrap();
+        self
+    }
+
+    pub fn polyline(&mut self, points: &[(f64, f64)], stroke: &str, width: f64) -> &mut Self {
+        let points = points
+            .iter()
+            .map(|&(x, y)| format!("{},{}", n(x), n(y)))
+            .collect::<Vec<_>>();
+        writeln!(
+            self.body,
+            r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="{}"/>"#,
+            points.join(" "),
+            stroke,
+            n(width)
+        )
+        .unwrap();
+        self
+    }
+
+    pub fn circle(&mut self, cx: f64, cy: f64, r: f64, fill: &str) -> &mut Self {
+        writeln!(
+            self.body,
+            r#"<circle cx="{}" cy="{}" r="{}" fill="{}"/>"#,
+            n(cx),
+            n(cy),
+            n(r),
+            fill
+        )
+        .unwrap();
+        self
+    }
+
+    /// Text centred on `(x, y)`.
+    pub fn text(&mut self, x: f64, y: f64, size: f64, text: &str) -> &mut Self {
+        writeln!(
+            self.body,
+            r#"<text x="{}" y="{}" font-size="{}" font-family="monospace" text-anchor="middle" dominant-baseline="middle">{}</text>"#,
+            n(x),
+            n(y),
+            n(size),
+            escape(text)
+        )
+        .unwrap();
+        self
+    }
+}
+
+impl Display for Svg {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        writeln!(
+            f,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#,
+            n(self.width),
+            n(self.height)
+        )?;
+        write!(f, "{}", self.body)?;
+        writeln!(f, "</svg>")
+    }
+}
+
+/// `value` with at most two decimals, which is plenty for pixels.
+fn n(value: f64) -> String {
+    let s = format!("{:.2}", value);
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    match s {
+        "-0" => String::from("0"),
+        s => s.to_owned(),
+    }
+}
+
+/// Side of a grid cell so that a `width` by `height` grid spans about 800
+/// pixels.
+pub fn cell_size(width: usize, height: usize) -> f64 {
+    (800.0 / width.max(height).max(1) as f64).clamp(1.0, 40.0)
+}
+
+/// Grey from white for `0` to black for `max`.
+pub fn grey(value: u32, max: u32) -> String {
+    let level = 255 - (value.min(max) * 255 / max.max(1)) as u8;
+    format!("#{0:02x}{0:02x}{0:02x}", level)
+}
+
+/// `s` with the characters that are special in XML and HTML escaped.
+pub fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '&' => out.push_str("&amp;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Writes `frames` to `dir` as `000.svg`, `001.svg` and so on, returning how
+/// many were written.
+pub fn write_frames(dir: &Path, frames: impl IntoIterator<Item = Svg>) -> io::Result<usize> {
+    fs::create_dir_all(dir)?;
+
+    let mut count = 0;
+    for (i, frame) in frames.into_iter().enumerate() {
+        fs::write(dir.join(format!("{:03}.svg", i)), frame.to_string())?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn document() {
+        let mut svg = Svg::new(100.0, 50.5);
+        svg.line((0.0, 0.0), (10.0, 2.5), "red", 1.0)
+            .polyline(&[(1.0, 2.0), (3.0, 4.0)], "blue", 2.0)
+            .circle(5.0, 5.0, 2.0, "green")
+            .text(50.0, 25.0, 12.0, "a<b");
+
+        assert_eq!(
+            svg.to_string(),
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="50.5" viewBox="0 0 100 50.5">
+<line x1="0" y1="0" x2="10" y2="2.5" stroke="red" stroke-width="1"/>
+<polyline points="1,2 3,4" fill="none" stroke="blue" stroke-width="2"/>
+<circle cx="5" cy="5" r="2" fill="green"/>
+<text x="50" y="25" font-size="12" font-family="monospace" text-anchor="middle" dominant-baseline="middle">a&lt;b</text>
+</svg>
+"#
+        );
+    }
+
+    #[test]
+    fn helpers() {
+        assert_eq!(grey(0, 9), "#ffffff");
+        assert_eq!(grey(9, 9), "#000000");
+        assert_eq!(grey(12, 9), "#000000");
+        assert_eq!(cell_size(10, 5), 40.0);
+        assert_eq!(cell_size(100, 100), 8.0);
+        assert_eq!(cell_size(2000, 10), 1.0);
+        assert_eq!(n(299.999999), "300");
+        assert_eq!(n(1.0 / 3.0), "0.33");
+        assert_eq!(n(-0.001), "0");
+        assert_eq!(
+            escape(r#"<a href="x">&'"#),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&#39;"
+        );
+    }
+
+    #[test]
+    fn frames() {
+        let dir = std::env::temp_dir().join(format!("common-frames-{}", std::process::id()));
+        let frames = (1..=2).map(|i| Svg::new(i as f64, 1.0));
+
+        assert_eq!(write_frames(&dir, frames).unwrap(), 2);
+        assert!(fs::read_to_string(dir.join("001.svg"))
+            .unwrap()
+            .contains(r#"width="2""#));
+        assert!(!dir.join("002.svg").exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
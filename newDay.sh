@@ -1,13 +1,6 @@
 #!/usr/bin/sh
 
+# Day to create, `18` for the configured year or `2022/01`
 day=$1
 
-
-mkdir $day
-cp -r ./.template/* $day
-
-set -a; 
-. ./.env; 
-set +a;
-
-curl -s --cookie "session=$AOC_SESSION" https://adventofcode.com/2021/day/$day/input >> $day/input.TXT
+cargo run --quiet --release --manifest-path ./aoc/Cargo.toml -- new $day